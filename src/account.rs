@@ -1,93 +1,519 @@
 use crate::{
     client::OkxClient,
     config::Config,
-    types::ApiResponse,
+    error::OkxError,
+    time::{opt_timestamp_ms, timestamp_ms},
+    types::{
+        decimal, format_decimal, AccountLevel, ApiResponse, InstType, Position, PositionMode,
+        PositionsParams,
+    },
 };
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-/// 账户余额信息
-#[derive(Debug, Serialize, Deserialize)]
+/// 账户余额，对应 `/api/v5/account/balance` 返回的 `data[]`
+///
+/// 账户级别字段仅适用于跨币种保证金和组合保证金模式，其他模式下为空字符串（解析为 `None`）。
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountBalance {
+    /// 美金层面权益
+    #[serde(rename = "totalEq", default, with = "decimal")]
+    pub total_eq: Option<Decimal>,
+    /// 美金层面逐仓仓位权益
+    #[serde(rename = "isoEq", default, with = "decimal")]
+    pub iso_eq: Option<Decimal>,
+    /// 美金层面有效保证金
+    #[serde(rename = "adjEq", default, with = "decimal")]
+    pub adj_eq: Option<Decimal>,
+    /// 美金层面全仓挂单占用保证金
+    #[serde(rename = "ordFroz", default, with = "decimal")]
+    pub ord_froz: Option<Decimal>,
+    /// 美金层面占用保证金
+    #[serde(default, with = "decimal")]
+    pub imr: Option<Decimal>,
+    /// 美金层面维持保证金
+    #[serde(default, with = "decimal")]
+    pub mmr: Option<Decimal>,
+    /// 美金层面潜在借币占用保证金
+    #[serde(rename = "borrowFroz", default, with = "decimal")]
+    pub borrow_froz: Option<Decimal>,
+    /// 美金层面保证金率
+    #[serde(rename = "mgnRatio", default, with = "decimal")]
+    pub mgn_ratio: Option<Decimal>,
+    /// 以美金价值为单位的持仓数量
+    #[serde(rename = "notionalUsd", default, with = "decimal")]
+    pub notional_usd: Option<Decimal>,
+    /// 账户层面全仓未实现盈亏（美元单位）
+    #[serde(default, with = "decimal")]
+    pub upl: Option<Decimal>,
+    /// 账户信息的更新时间
+    #[serde(rename = "uTime", with = "timestamp_ms")]
+    pub u_time: DateTime<Utc>,
+    /// 各币种资产详细信息
+    #[serde(default)]
+    pub details: Vec<BalanceDetail>,
+}
+
+/// 单个币种的资产详情
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceDetail {
     /// 币种
-    pub ccy: Option<String>,
+    pub ccy: String,
+    /// 币种总权益
+    #[serde(default, with = "decimal")]
+    pub eq: Option<Decimal>,
+    /// 币种余额
+    #[serde(rename = "cashBal", default, with = "decimal")]
+    pub cash_bal: Option<Decimal>,
+    /// 币种逐仓仓位权益
+    #[serde(rename = "isoEq", default, with = "decimal")]
+    pub iso_eq: Option<Decimal>,
+    /// 可用保证金
+    #[serde(rename = "availEq", default, with = "decimal")]
+    pub avail_eq: Option<Decimal>,
+    /// 美金层面币种折算权益
+    #[serde(rename = "disEq", default, with = "decimal")]
+    pub dis_eq: Option<Decimal>,
+    /// 抄底宝、逃顶宝功能的币种冻结金额
+    #[serde(rename = "fixedBal", default, with = "decimal")]
+    pub fixed_bal: Option<Decimal>,
     /// 可用余额
-    #[serde(rename = "availBal")]
-    pub avail_bal: Option<String>,
-    /// 余额
-    pub bal: Option<String>,
-    /// 冻结余额
-    #[serde(rename = "frozenBal")]
-    pub frozen_bal: Option<String>,
-    /// 账户类型
-    #[serde(rename = "acctType")]
-    pub acct_type: Option<String>,
-}
-
-/// 账户配置信息
-#[derive(Debug, Serialize, Deserialize)]
+    #[serde(rename = "availBal", default, with = "decimal")]
+    pub avail_bal: Option<Decimal>,
+    /// 币种占用金额
+    #[serde(rename = "frozenBal", default, with = "decimal")]
+    pub frozen_bal: Option<Decimal>,
+    /// 挂单冻结数量
+    #[serde(rename = "ordFrozen", default, with = "decimal")]
+    pub ord_frozen: Option<Decimal>,
+    /// 币种负债额，为负数
+    #[serde(default, with = "decimal")]
+    pub liab: Option<Decimal>,
+    /// 未实现盈亏
+    #[serde(default, with = "decimal")]
+    pub upl: Option<Decimal>,
+    /// 由于仓位未实现亏损导致的负债
+    #[serde(rename = "uplLiab", default, with = "decimal")]
+    pub upl_liab: Option<Decimal>,
+    /// 币种全仓负债额
+    #[serde(rename = "crossLiab", default, with = "decimal")]
+    pub cross_liab: Option<Decimal>,
+    /// 币种逐仓负债额
+    #[serde(rename = "isoLiab", default, with = "decimal")]
+    pub iso_liab: Option<Decimal>,
+    /// 币种维度全仓占用保证金
+    #[serde(default, with = "decimal")]
+    pub imr: Option<Decimal>,
+    /// 币种维度全仓维持保证金
+    #[serde(default, with = "decimal")]
+    pub mmr: Option<Decimal>,
+    /// 币种维度全仓保证金率
+    #[serde(rename = "mgnRatio", default, with = "decimal")]
+    pub mgn_ratio: Option<Decimal>,
+    /// 计息，应扣未扣利息
+    #[serde(default, with = "decimal")]
+    pub interest: Option<Decimal>,
+    /// 自动借币风险等级（0-5），数值越大越可能触发自动借币
+    #[serde(default, with = "decimal")]
+    pub twap: Option<Decimal>,
+    /// 币种最大可借
+    #[serde(rename = "maxLoan", default, with = "decimal")]
+    pub max_loan: Option<Decimal>,
+    /// 币种权益美金价值
+    #[serde(rename = "eqUsd", default, with = "decimal")]
+    pub eq_usd: Option<Decimal>,
+    /// 币种美金层面潜在借币占用保证金
+    #[serde(rename = "borrowFroz", default, with = "decimal")]
+    pub borrow_froz: Option<Decimal>,
+    /// 币种杠杆倍数
+    #[serde(rename = "notionalLever", default, with = "decimal")]
+    pub notional_lever: Option<Decimal>,
+    /// 策略权益
+    #[serde(rename = "stgyEq", default, with = "decimal")]
+    pub stgy_eq: Option<Decimal>,
+    /// 逐仓未实现盈亏
+    #[serde(rename = "isoUpl", default, with = "decimal")]
+    pub iso_upl: Option<Decimal>,
+    /// 现货对冲占用数量（组合保证金模式）
+    #[serde(rename = "spotInUseAmt", default, with = "decimal")]
+    pub spot_in_use_amt: Option<Decimal>,
+    /// 现货余额
+    #[serde(rename = "spotBal", default, with = "decimal")]
+    pub spot_bal: Option<Decimal>,
+    /// 现货累计盈亏
+    #[serde(rename = "totalPnl", default, with = "decimal")]
+    pub total_pnl: Option<Decimal>,
+    /// 币种余额信息的更新时间
+    #[serde(rename = "uTime", default, with = "opt_timestamp_ms")]
+    pub u_time: Option<DateTime<Utc>>,
+}
+
+impl BalanceDetail {
+    /// 表格表头，与 [`BalanceDetail::format_display`] 的列对齐
+    pub fn table_header() -> String {
+        format!(
+            "{:<8} | {:<16} | {:<16} | {:<16} | {:<14} | {:<12} | {:<12} | {:<14} | {:<4}",
+            "Ccy", "Eq", "CashBal", "AvailBal", "Upl", "Liab", "Interest", "EqUsd", "Twap"
+        )
+    }
+
+    /// 表格格式输出
+    pub fn format_display(&self) -> String {
+        format!(
+            "{:<8} | {:<16} | {:<16} | {:<16} | {:<14} | {:<12} | {:<12} | {:<14} | {:<4}",
+            self.ccy,
+            format_decimal(&self.eq),
+            format_decimal(&self.cash_bal),
+            format_decimal(&self.avail_bal),
+            format_decimal(&self.upl),
+            format_decimal(&self.liab),
+            format_decimal(&self.interest),
+            format_decimal(&self.eq_usd),
+            format_decimal(&self.twap)
+        )
+    }
+}
+
+impl AccountBalance {
+    /// 账户层面汇总，一行输出
+    pub fn format_summary(&self) -> String {
+        format!(
+            "总权益: {} USD | 有效保证金: {} | 占用保证金: {} | 维持保证金: {} | 保证金率: {} | 持仓价值: {} USD",
+            format_decimal(&self.total_eq),
+            format_decimal(&self.adj_eq),
+            format_decimal(&self.imr),
+            format_decimal(&self.mmr),
+            format_decimal(&self.mgn_ratio),
+            format_decimal(&self.notional_usd)
+        )
+    }
+}
+
+/// 账户配置，对应 `/api/v5/account/config` 返回的 `data[]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountConfig {
-    /// 账户ID
-    #[serde(rename = "acctId")]
-    pub acct_id: Option<String>,
-    /// 账户类型
-    #[serde(rename = "acctType")]
-    pub acct_type: Option<String>,
-    /// 币种
-    pub ccy: Option<String>,
-    /// 是否支持交易
-    #[serde(rename = "tradingEnabled")]
-    pub trading_enabled: Option<bool>,
-    /// 是否支持提现
-    #[serde(rename = "withdrawEnabled")]
-    pub withdraw_enabled: Option<bool>,
-    /// 是否支持充值
-    #[serde(rename = "depositEnabled")]
-    pub deposit_enabled: Option<bool>,
+    /// 当前请求的账户ID
+    pub uid: String,
+    /// 母账户ID，与 `uid` 相同时表示当前为母账户
+    #[serde(rename = "mainUid")]
+    pub main_uid: String,
+    /// 账户模式
+    #[serde(rename = "acctLv")]
+    pub acct_lv: AccountLevel,
+    /// 持仓模式
+    #[serde(rename = "posMode")]
+    pub pos_mode: PositionMode,
+    /// 是否自动借币
+    #[serde(rename = "autoLoan", default)]
+    pub auto_loan: bool,
+    /// 当前希腊字母展示方式：PA 币本位，BS 美元本位
+    #[serde(rename = "greeksType", default)]
+    pub greeks_type: String,
+    /// 当前在平台上真实交易量的用户等级，如 `Lv1`
+    #[serde(default)]
+    pub level: String,
+    /// 特约用户的临时体验用户等级
+    #[serde(rename = "levelTmp", default)]
+    pub level_tmp: String,
+    /// 衍生品的逐仓保证金划转模式：automatic 开仓划转，autonomy 自主划转
+    #[serde(rename = "ctIsoMode", default)]
+    pub ct_iso_mode: String,
+    /// 币币杠杆的逐仓保证金划转模式：automatic 开仓划转，quick_margin 一键借币
+    #[serde(rename = "mgnIsoMode", default)]
+    pub mgn_iso_mode: String,
+    /// API key 权限，逗号分隔，如 `read_only,trade,withdraw`
+    #[serde(default)]
+    pub perm: String,
+    /// API key 绑定的IP地址，多个以逗号分隔，未绑定时为空
+    #[serde(default)]
+    pub ip: String,
+    /// API key 备注名
+    #[serde(default)]
+    pub label: String,
+    /// 账户类型：0 母账户，1 普通子账户，2 资管子账户，5 托管交易子账户
+    #[serde(rename = "type", default)]
+    pub account_type: String,
+    /// 是否允许现货模式下借币
+    #[serde(rename = "enableSpotBorrow", default)]
+    pub enable_spot_borrow: bool,
+}
+
+impl AccountConfig {
+    /// API key 权限列表
+    pub fn permissions(&self) -> Vec<&str> {
+        self.perm
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .collect()
+    }
+
+    /// 是否为母账户
+    pub fn is_main_account(&self) -> bool {
+        self.uid == self.main_uid
+    }
+
+    /// 可读的多行摘要
+    pub fn format_summary(&self) -> String {
+        let account = if self.is_main_account() {
+            "母账户".to_string()
+        } else {
+            format!("子账户 (母账户 {})", self.main_uid)
+        };
+        let ip = if self.ip.is_empty() {
+            "未绑定"
+        } else {
+            &self.ip
+        };
+        let label = if self.label.is_empty() {
+            "-"
+        } else {
+            &self.label
+        };
+
+        [
+            format!("账户ID:       {} ({})", self.uid, account),
+            format!(
+                "账户模式:     {} (acctLv={})",
+                self.acct_lv.description(),
+                self.acct_lv
+            ),
+            format!(
+                "持仓模式:     {} ({})",
+                self.pos_mode.description(),
+                self.pos_mode
+            ),
+            format!("自动借币:     {}", if self.auto_loan { "是" } else { "否" }),
+            format!("希腊字母:     {}", self.greeks_type),
+            format!("用户等级:     {}", self.level),
+            format!("衍生品逐仓:   {}", self.ct_iso_mode),
+            format!("杠杆逐仓:     {}", self.mgn_iso_mode),
+            format!("API权限:      {}", self.permissions().join(", ")),
+            format!("绑定IP:       {}", ip),
+            format!("API备注:      {}", label),
+        ]
+        .join("\n")
+    }
+}
+
+impl OkxClient {
+    /// 获取账户余额
+    pub async fn get_account_balance(&self) -> Result<ApiResponse<AccountBalance>, OkxError> {
+        // 执行API请求 - 不需要查询参数
+        self.get("/api/v5/account/balance", None).await
+    }
+
+    /// 获取账户配置
+    pub async fn get_account_config(&self) -> Result<ApiResponse<AccountConfig>, OkxError> {
+        // 执行API请求 - 不需要查询参数
+        self.get("/api/v5/account/config", None).await
+    }
+
+    /// 获取指定币种的账户余额
+    pub async fn get_account_balance_by_currency(
+        &self,
+        ccy: &str,
+    ) -> Result<ApiResponse<AccountBalance>, OkxError> {
+        // 构建查询参数
+        let mut query_params = HashMap::new();
+        query_params.insert("ccy".to_string(), ccy.to_string());
+
+        // 执行API请求
+        self.get("/api/v5/account/balance", Some(&query_params))
+            .await
+    }
+}
+
+/// 未成交订单（切换前检查用的精简字段）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingOrder {
+    /// 产品类型
+    #[serde(rename = "instType")]
+    pub inst_type: InstType,
+    /// 产品ID
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    /// 订单ID
+    #[serde(rename = "ordId")]
+    pub ord_id: String,
+    /// 订单方向
+    #[serde(default)]
+    pub side: String,
+    /// 委托数量
+    #[serde(default, with = "decimal")]
+    pub sz: Option<Decimal>,
+}
+
+/// 设置持仓模式的返回
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetPositionModeResult {
+    #[serde(rename = "posMode")]
+    pub pos_mode: PositionMode,
+}
+
+/// 设置账户模式的返回
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetAccountLevelResult {
+    #[serde(rename = "acctLv")]
+    pub acct_lv: AccountLevel,
+}
+
+/// 切换持仓模式或账户模式前的检查结果
+///
+/// OKX 要求切换前没有相关持仓和挂单，否则会拒绝请求。
+#[derive(Debug, Clone, Default)]
+pub struct SwitchBlockers {
+    /// 未平仓的持仓
+    pub positions: Vec<Position>,
+    /// 未成交的订单
+    pub orders: Vec<PendingOrder>,
+}
+
+impl SwitchBlockers {
+    /// 没有阻止切换的持仓和挂单
+    pub fn is_clear(&self) -> bool {
+        self.positions.is_empty() && self.orders.is_empty()
+    }
+
+    /// 说明阻止切换的原因
+    pub fn explain(&self) -> String {
+        let mut lines = Vec::new();
+        if !self.positions.is_empty() {
+            lines.push(format!("存在 {} 个未平仓持仓:", self.positions.len()));
+            for position in &self.positions {
+                lines.push(format!(
+                    "  {} {} {}",
+                    position.inst_id,
+                    position.pos_side,
+                    format_decimal(&position.pos)
+                ));
+            }
+        }
+        if !self.orders.is_empty() {
+            lines.push(format!("存在 {} 个未成交订单:", self.orders.len()));
+            for order in &self.orders {
+                lines.push(format!(
+                    "  {} {} {} (ordId {})",
+                    order.inst_id,
+                    order.side,
+                    format_decimal(&order.sz),
+                    order.ord_id
+                ));
+            }
+        }
+        lines.push("请先平仓并撤销所有挂单后再切换".to_string());
+        lines.join("\n")
+    }
+}
+
+/// 持仓模式只影响交割和永续合约
+const POSITION_MODE_INST_TYPES: &[InstType] = &[InstType::Futures, InstType::Swap];
+
+impl OkxClient {
+    /// 获取未成交订单
+    pub async fn get_pending_orders(
+        &self,
+        inst_type: Option<&InstType>,
+    ) -> Result<ApiResponse<PendingOrder>, OkxError> {
+        let mut query_params = HashMap::new();
+        if let Some(inst_type) = inst_type {
+            query_params.insert("instType".to_string(), inst_type.to_string());
+        }
+
+        self.get("/api/v5/trade/orders-pending", Some(&query_params))
+            .await
+    }
+
+    /// 检查指定产品类型（为空时检查全部）下阻止切换的持仓和挂单
+    pub async fn check_switch_blockers(
+        &self,
+        inst_types: &[InstType],
+    ) -> Result<SwitchBlockers, OkxError> {
+        let relevant =
+            |inst_type: &InstType| inst_types.is_empty() || inst_types.contains(inst_type);
+
+        let positions = self
+            .get_positions(&PositionsParams::default())
+            .await?
+            .data
+            .into_iter()
+            .filter(|p| relevant(&p.inst_type))
+            .filter(|p| p.pos.is_some_and(|pos| !pos.is_zero()))
+            .collect();
+        let orders = self
+            .get_pending_orders(None)
+            .await?
+            .data
+            .into_iter()
+            .filter(|o| relevant(&o.inst_type))
+            .collect();
+
+        Ok(SwitchBlockers { positions, orders })
+    }
+
+    /// 检查切换持仓模式前需要处理的交割、永续持仓和挂单
+    pub async fn check_position_mode_switch(&self) -> Result<SwitchBlockers, OkxError> {
+        self.check_switch_blockers(POSITION_MODE_INST_TYPES).await
+    }
+
+    /// 检查切换账户模式前需要处理的持仓和挂单
+    pub async fn check_account_level_switch(&self) -> Result<SwitchBlockers, OkxError> {
+        self.check_switch_blockers(&[]).await
+    }
+
+    /// 设置持仓模式
+    ///
+    /// 不做切换前检查，有交割、永续持仓或挂单时 OKX 会拒绝，
+    /// 可先调用 [`OkxClient::check_position_mode_switch`]。
+    pub async fn set_position_mode(
+        &self,
+        pos_mode: &PositionMode,
+    ) -> Result<ApiResponse<SetPositionModeResult>, OkxError> {
+        let body = serde_json::json!({ "posMode": pos_mode });
+        self.post("/api/v5/account/set-position-mode", Some(&body))
+            .await
+    }
+
+    /// 设置账户模式
+    ///
+    /// 不做切换前检查，可先调用 [`OkxClient::check_account_level_switch`]。
+    pub async fn set_account_level(
+        &self,
+        acct_lv: &AccountLevel,
+    ) -> Result<ApiResponse<SetAccountLevelResult>, OkxError> {
+        let body = serde_json::json!({ "acctLv": acct_lv });
+        self.post("/api/v5/account/set-account-level", Some(&body))
+            .await
+    }
 }
 
 /// 获取账户余额
+///
+/// 兼容旧接口：每次调用都会创建新的客户端，建议使用 [`OkxClient::get_account_balance`]。
 pub async fn get_account_balance(config: &Config) -> Result<ApiResponse<AccountBalance>> {
-    let client = OkxClient::new(config.clone());
-    
-    // 执行API请求 - 不需要查询参数
-    let response: ApiResponse<AccountBalance> = client
-        .get("/api/v5/account/balance", None)
-        .await?;
-
-    Ok(response)
+    Ok(OkxClient::new(config.clone()).get_account_balance().await?)
 }
 
 /// 获取账户配置
+///
+/// 兼容旧接口：每次调用都会创建新的客户端，建议使用 [`OkxClient::get_account_config`]。
 pub async fn get_account_config(config: &Config) -> Result<ApiResponse<AccountConfig>> {
-    let client = OkxClient::new(config.clone());
-    
-    // 执行API请求 - 不需要查询参数
-    let response: ApiResponse<AccountConfig> = client
-        .get("/api/v5/account/config", None)
-        .await?;
-
-    Ok(response)
+    Ok(OkxClient::new(config.clone()).get_account_config().await?)
 }
 
 /// 获取指定币种的账户余额
+///
+/// 兼容旧接口：每次调用都会创建新的客户端，建议使用 [`OkxClient::get_account_balance_by_currency`]。
 pub async fn get_account_balance_by_currency(
     config: &Config,
     ccy: &str,
 ) -> Result<ApiResponse<AccountBalance>> {
-    let client = OkxClient::new(config.clone());
-    
-    // 构建查询参数
-    let mut query_params = HashMap::new();
-    query_params.insert("ccy".to_string(), ccy.to_string());
-    
-    // 执行API请求
-    let response: ApiResponse<AccountBalance> = client
-        .get("/api/v5/account/balance", Some(&query_params))
-        .await?;
-
-    Ok(response)
+    Ok(OkxClient::new(config.clone())
+        .get_account_balance_by_currency(ccy)
+        .await?)
 }
 
 #[cfg(test)]
@@ -95,40 +521,132 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_account_balance_deserialization() {
+    fn test_account_balance_fixture() {
+        let json = include_str!("../fixtures/account_balance.json");
+        let response: ApiResponse<AccountBalance> = serde_json::from_str(json).unwrap();
+
+        let balance = &response.data[0];
+        assert_eq!(balance.total_eq, Some("55837.43556134779".parse().unwrap()));
+        assert_eq!(balance.adj_eq, Some("55415.624719833286".parse().unwrap()));
+        assert_eq!(
+            balance.mgn_ratio,
+            Some("143682.59776662575".parse().unwrap())
+        );
+        assert_eq!(balance.notional_usd, Some("85.7068529".parse().unwrap()));
+        assert_eq!(balance.u_time.timestamp_millis(), 1705474164160);
+        assert_eq!(balance.details.len(), 2);
+
+        let usdt = &balance.details[0];
+        assert_eq!(usdt.ccy, "USDT");
+        assert_eq!(usdt.eq, Some("4992.890093622894".parse().unwrap()));
+        assert_eq!(usdt.cash_bal, Some("4850.435693622894".parse().unwrap()));
+        assert_eq!(usdt.upl, Some("-7.545600000000006".parse().unwrap()));
+        assert_eq!(usdt.imr, None);
+        assert_eq!(usdt.mgn_ratio, None);
+
+        let btc = &balance.details[1];
+        assert_eq!(btc.liab, Some("-0.01".parse().unwrap()));
+        assert_eq!(btc.interest, Some("0.0000012".parse().unwrap()));
+        assert_eq!(btc.twap, Some(Decimal::from(2)));
+        assert_eq!(btc.notional_lever, None);
+    }
+
+    #[test]
+    fn test_account_balance_single_currency_mode() {
+        // 简单交易模式下账户层面的保证金字段为空字符串
         let json = r#"{
-            "ccy": "BTC",
-            "availBal": "1.0",
-            "bal": "1.5",
-            "frozenBal": "0.5",
-            "acctType": "18"
+            "adjEq": "",
+            "imr": "",
+            "isoEq": "",
+            "mgnRatio": "",
+            "mmr": "",
+            "notionalUsd": "",
+            "ordFroz": "",
+            "totalEq": "1.5",
+            "uTime": "1705474164160",
+            "details": [{"ccy": "BTC", "eq": "1.5", "availBal": "1.0", "uTime": ""}]
         }"#;
 
         let balance: AccountBalance = serde_json::from_str(json).unwrap();
-        assert_eq!(balance.ccy, Some("BTC".to_string()));
-        assert_eq!(balance.avail_bal, Some("1.0".to_string()));
-        assert_eq!(balance.bal, Some("1.5".to_string()));
-        assert_eq!(balance.frozen_bal, Some("0.5".to_string()));
-        assert_eq!(balance.acct_type, Some("18".to_string()));
+        assert_eq!(balance.total_eq, Some("1.5".parse().unwrap()));
+        assert_eq!(balance.adj_eq, None);
+        assert_eq!(balance.details[0].avail_bal, Some("1.0".parse().unwrap()));
+        assert_eq!(balance.details[0].u_time, None);
+        assert_eq!(balance.details[0].cash_bal, None);
     }
 
     #[test]
-    fn test_account_config_deserialization() {
-        let json = r#"{
-            "acctId": "123456",
-            "acctType": "18",
-            "ccy": "BTC",
-            "tradingEnabled": true,
-            "withdrawEnabled": true,
-            "depositEnabled": true
-        }"#;
+    fn test_balance_detail_table_row() {
+        let json = include_str!("../fixtures/account_balance.json");
+        let response: ApiResponse<AccountBalance> = serde_json::from_str(json).unwrap();
+
+        let row = response.data[0].details[1].format_display();
+        assert!(row.starts_with("BTC"));
+        assert!(row.contains("0.4912"));
+        assert!(row.contains("-0.01"));
+        assert_eq!(
+            row.matches('|').count(),
+            BalanceDetail::table_header().matches('|').count()
+        );
+    }
+
+    #[test]
+    fn test_account_config_fixture() {
+        let json = include_str!("../fixtures/account_config.json");
+        let response: ApiResponse<AccountConfig> = serde_json::from_str(json).unwrap();
+
+        let config = &response.data[0];
+        assert_eq!(config.uid, "44705892343619584");
+        assert!(config.is_main_account());
+        assert_eq!(config.acct_lv, AccountLevel::Futures);
+        assert_eq!(config.pos_mode, PositionMode::LongShort);
+        assert!(!config.auto_loan);
+        assert_eq!(config.greeks_type, "PA");
+        assert_eq!(config.level, "Lv1");
+        assert_eq!(config.ct_iso_mode, "automatic");
+        assert_eq!(config.permissions(), vec!["read_only", "withdraw", "trade"]);
+        assert_eq!(config.label, "v5 test");
+    }
+
+    #[test]
+    fn test_account_config_summary() {
+        let json = include_str!("../fixtures/account_config.json");
+        let response: ApiResponse<AccountConfig> = serde_json::from_str(json).unwrap();
+
+        let summary = response.data[0].format_summary();
+        assert!(summary.contains("合约模式 (acctLv=2)"));
+        assert!(summary.contains("开平仓模式 (long_short_mode)"));
+        assert!(summary.contains("绑定IP:       未绑定"));
+        assert!(summary.contains("read_only, withdraw, trade"));
+    }
+
+    #[test]
+    fn test_switch_blockers_explain() {
+        let json = r#"{"code": "0", "msg": "", "data": [
+            {"instType": "SWAP", "instId": "BTC-USDT-SWAP", "ordId": "123", "side": "buy", "sz": "2"}
+        ]}"#;
+        let orders: ApiResponse<PendingOrder> = serde_json::from_str(json).unwrap();
+
+        let blockers = SwitchBlockers::default();
+        assert!(blockers.is_clear());
 
-        let config: AccountConfig = serde_json::from_str(json).unwrap();
-        assert_eq!(config.acct_id, Some("123456".to_string()));
-        assert_eq!(config.acct_type, Some("18".to_string()));
-        assert_eq!(config.ccy, Some("BTC".to_string()));
-        assert_eq!(config.trading_enabled, Some(true));
-        assert_eq!(config.withdraw_enabled, Some(true));
-        assert_eq!(config.deposit_enabled, Some(true));
+        let blockers = SwitchBlockers {
+            positions: Vec::new(),
+            orders: orders.data,
+        };
+        assert!(!blockers.is_clear());
+        let explanation = blockers.explain();
+        assert!(explanation.contains("存在 1 个未成交订单"));
+        assert!(explanation.contains("BTC-USDT-SWAP buy 2 (ordId 123)"));
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_set_mode_results() {
+        let result: SetPositionModeResult =
+            serde_json::from_str(r#"{"posMode": "net_mode"}"#).unwrap();
+        assert_eq!(result.pos_mode, PositionMode::Net);
+
+        let result: SetAccountLevelResult = serde_json::from_str(r#"{"acctLv": "3"}"#).unwrap();
+        assert_eq!(result.acct_lv, AccountLevel::MultiCurrency);
+    }
+}
@@ -0,0 +1,422 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{
+    client::OkxClient,
+    error::OkxError,
+    pager::page_limit,
+    time::{opt_timestamp_ms, timestamp_ms, DisplayTz},
+    types::{
+        decimal, format_decimal, opt_enum, ApiResponse, BillSubType, BillType, InstType, MarginMode,
+    },
+};
+
+/// 账单流水
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bill {
+    /// 账单ID
+    #[serde(rename = "billId")]
+    pub bill_id: String,
+    /// 产品类型，划转等非交易账单为空
+    #[serde(rename = "instType", default, with = "opt_enum")]
+    pub inst_type: Option<InstType>,
+    /// 产品ID
+    #[serde(rename = "instId", default)]
+    pub inst_id: String,
+    /// 账单类型
+    #[serde(rename = "type")]
+    pub bill_type: BillType,
+    /// 账单子类型
+    #[serde(rename = "subType")]
+    pub sub_type: BillSubType,
+    /// 账户余额币种
+    pub ccy: String,
+    /// 账户层面的余额变动数量
+    #[serde(rename = "balChg", default, with = "decimal")]
+    pub bal_chg: Option<Decimal>,
+    /// 账户层面的余额数量
+    #[serde(default, with = "decimal")]
+    pub bal: Option<Decimal>,
+    /// 仓位层面的余额变动数量
+    #[serde(rename = "posBalChg", default, with = "decimal")]
+    pub pos_bal_chg: Option<Decimal>,
+    /// 仓位层面的余额数量
+    #[serde(rename = "posBal", default, with = "decimal")]
+    pub pos_bal: Option<Decimal>,
+    /// 数量
+    #[serde(default, with = "decimal")]
+    pub sz: Option<Decimal>,
+    /// 成交价格等，与账单类型有关
+    #[serde(default, with = "decimal")]
+    pub px: Option<Decimal>,
+    /// 收益
+    #[serde(default, with = "decimal")]
+    pub pnl: Option<Decimal>,
+    /// 手续费，负数代表平台扣除，正数代表返佣
+    #[serde(default, with = "decimal")]
+    pub fee: Option<Decimal>,
+    /// 利息
+    #[serde(default, with = "decimal")]
+    pub interest: Option<Decimal>,
+    /// 保证金模式：isolated、cross、cash，非交易账单为空
+    #[serde(rename = "mgnMode", default)]
+    pub mgn_mode: String,
+    /// 订单ID
+    #[serde(rename = "ordId", default)]
+    pub ord_id: String,
+    /// 客户自定义订单ID
+    #[serde(rename = "clOrdId", default)]
+    pub cl_ord_id: String,
+    /// 最新一笔成交ID
+    #[serde(rename = "tradeId", default)]
+    pub trade_id: String,
+    /// 流动性方向：T taker，M maker
+    #[serde(rename = "execType", default)]
+    pub exec_type: String,
+    /// 转出账户类型，仅划转账单有值
+    #[serde(default)]
+    pub from: String,
+    /// 转入账户类型，仅划转账单有值
+    #[serde(default)]
+    pub to: String,
+    /// 备注
+    #[serde(default)]
+    pub notes: String,
+    /// 订单标签
+    #[serde(default)]
+    pub tag: String,
+    /// 最新成交时间
+    #[serde(rename = "fillTime", default, with = "opt_timestamp_ms")]
+    pub fill_time: Option<DateTime<Utc>>,
+    /// 账单创建时间
+    #[serde(with = "timestamp_ms")]
+    pub ts: DateTime<Utc>,
+}
+
+impl Bill {
+    /// 表格表头，与 [`Bill::format_display`] 的列对齐
+    pub fn table_header() -> String {
+        format!(
+            "{:<29} | {:<18} | {:<10} | {:<7} | {:<6} | {:<16} | {:<18} | {:<12} | {:<12} | {:<16}",
+            "Time", "BillId", "Type", "SubType", "Ccy", "BalChg", "Bal", "Fee", "Pnl", "InstId"
+        )
+    }
+
+    /// 表格格式输出，时间按 `tz` 时区显示
+    pub fn format_display(&self, tz: DisplayTz) -> String {
+        format!(
+            "{:<29} | {:<18} | {:<10} | {:<7} | {:<6} | {:<16} | {:<18} | {:<12} | {:<12} | {:<16}",
+            tz.format(&self.ts),
+            self.bill_id,
+            self.bill_type.description(),
+            self.sub_type,
+            self.ccy,
+            format_decimal(&self.bal_chg),
+            format_decimal(&self.bal),
+            format_decimal(&self.fee),
+            format_decimal(&self.pnl),
+            self.inst_id
+        )
+    }
+}
+
+/// 账单来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BillsSource {
+    /// 近7天账单
+    Recent,
+    /// 近3个月账单
+    Archive,
+}
+
+impl BillsSource {
+    pub fn endpoint(&self) -> &'static str {
+        match self {
+            BillsSource::Recent => "/api/v5/account/bills",
+            BillsSource::Archive => "/api/v5/account/bills-archive",
+        }
+    }
+}
+
+/// 账单查询参数
+///
+/// `after`/`before` 为 `billId` 游标，`begin`/`end` 为毫秒时间戳范围。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BillsParams {
+    pub inst_type: Option<InstType>,
+    pub inst_id: Option<String>,
+    pub ccy: Option<String>,
+    pub mgn_mode: Option<MarginMode>,
+    pub bill_type: Option<BillType>,
+    pub sub_type: Option<BillSubType>,
+    pub after: Option<String>,
+    pub before: Option<String>,
+    pub begin: Option<String>,
+    pub end: Option<String>,
+    pub limit: Option<String>,
+}
+
+impl BillsParams {
+    pub fn to_query_params(&self) -> HashMap<String, String> {
+        let mut params = HashMap::new();
+
+        if let Some(ref inst_type) = self.inst_type {
+            params.insert("instType".to_string(), inst_type.to_string());
+        }
+        if let Some(ref inst_id) = self.inst_id {
+            params.insert("instId".to_string(), inst_id.clone());
+        }
+        if let Some(ref ccy) = self.ccy {
+            params.insert("ccy".to_string(), ccy.clone());
+        }
+        if let Some(ref mgn_mode) = self.mgn_mode {
+            params.insert("mgnMode".to_string(), mgn_mode.to_string());
+        }
+        if let Some(ref bill_type) = self.bill_type {
+            params.insert("type".to_string(), bill_type.to_string());
+        }
+        if let Some(ref sub_type) = self.sub_type {
+            params.insert("subType".to_string(), sub_type.to_string());
+        }
+        if let Some(ref after) = self.after {
+            params.insert("after".to_string(), after.clone());
+        }
+        if let Some(ref before) = self.before {
+            params.insert("before".to_string(), before.clone());
+        }
+        if let Some(ref begin) = self.begin {
+            params.insert("begin".to_string(), begin.clone());
+        }
+        if let Some(ref end) = self.end {
+            params.insert("end".to_string(), end.clone());
+        }
+        if let Some(ref limit) = self.limit {
+            params.insert("limit".to_string(), limit.clone());
+        }
+
+        params
+    }
+}
+
+impl OkxClient {
+    /// 获取近7天的账单流水
+    pub async fn get_bills(&self, params: &BillsParams) -> Result<ApiResponse<Bill>, OkxError> {
+        self.get_bills_from(BillsSource::Recent, params).await
+    }
+
+    /// 获取近3个月的账单流水
+    pub async fn get_bills_archive(
+        &self,
+        params: &BillsParams,
+    ) -> Result<ApiResponse<Bill>, OkxError> {
+        self.get_bills_from(BillsSource::Archive, params).await
+    }
+
+    /// 从指定来源获取一页账单流水
+    pub async fn get_bills_from(
+        &self,
+        source: BillsSource,
+        params: &BillsParams,
+    ) -> Result<ApiResponse<Bill>, OkxError> {
+        let query_params = params.to_query_params();
+        self.get(source.endpoint(), Some(&query_params)).await
+    }
+}
+
+/// 账单分页查询
+///
+/// 账单按 `billId` 从新到旧返回，下一页以本页最后一条的 `billId` 作为 `after` 游标。
+/// `billId` 唯一，因此不需要去重；时间范围由服务端按 `begin`/`end` 过滤。
+pub struct BillsPager {
+    client: OkxClient,
+    source: BillsSource,
+    params: BillsParams,
+    limit: usize,
+    cursor: Option<String>,
+    done: bool,
+}
+
+impl BillsPager {
+    pub fn new(client: OkxClient, source: BillsSource, params: BillsParams) -> Self {
+        let limit = page_limit(params.limit.as_deref());
+        let cursor = params.after.clone();
+
+        Self {
+            client,
+            source,
+            params,
+            limit,
+            cursor,
+            done: false,
+        }
+    }
+
+    /// 获取下一页记录，没有更多数据时返回 `None`
+    pub async fn next_page(&mut self) -> Result<Option<Vec<Bill>>, OkxError> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let mut params = self.params.clone();
+        params.after = self.cursor.clone();
+        params.limit = Some(self.limit.to_string());
+
+        let page = self.client.get_bills_from(self.source, &params).await?.data;
+        let records = self.advance(page);
+        Ok(if records.is_empty() {
+            None
+        } else {
+            Some(records)
+        })
+    }
+
+    /// 获取所有剩余记录
+    pub async fn collect_all(mut self) -> Result<Vec<Bill>, OkxError> {
+        let mut all = Vec::new();
+        while let Some(records) = self.next_page().await? {
+            all.extend(records);
+        }
+        Ok(all)
+    }
+
+    /// 处理一页原始数据：更新游标和结束状态
+    fn advance(&mut self, page: Vec<Bill>) -> Vec<Bill> {
+        match page.last() {
+            Some(last) => self.cursor = Some(last.bill_id.clone()),
+            None => self.done = true,
+        }
+        if page.len() < self.limit {
+            self.done = true;
+        }
+        page
+    }
+}
+
+impl OkxClient {
+    /// 创建账单分页查询
+    pub fn bills_pager(&self, source: BillsSource, params: BillsParams) -> BillsPager {
+        BillsPager::new(self.clone(), source, params)
+    }
+
+    /// 自动翻页获取所有账单
+    pub async fn get_bills_all(
+        &self,
+        source: BillsSource,
+        params: &BillsParams,
+    ) -> Result<Vec<Bill>, OkxError> {
+        self.bills_pager(source, params.clone()).collect_all().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn fixture() -> Vec<Bill> {
+        let json = include_str!("../fixtures/account_bills.json");
+        let response: ApiResponse<Bill> = serde_json::from_str(json).unwrap();
+        response.data
+    }
+
+    #[test]
+    fn test_bills_params_serialization() {
+        let params = BillsParams {
+            inst_type: Some(InstType::Swap),
+            ccy: Some("USDT".to_string()),
+            bill_type: Some(BillType::FundingFee),
+            sub_type: Some(BillSubType::FundingFeeExpense),
+            begin: Some("1695000000000".to_string()),
+            limit: Some("50".to_string()),
+            ..Default::default()
+        };
+
+        let query_params = params.to_query_params();
+        assert_eq!(query_params.get("instType"), Some(&"SWAP".to_string()));
+        assert_eq!(query_params.get("type"), Some(&"8".to_string()));
+        assert_eq!(query_params.get("subType"), Some(&"173".to_string()));
+        assert_eq!(
+            query_params.get("begin"),
+            Some(&"1695000000000".to_string())
+        );
+        assert_eq!(query_params.get("instId"), None);
+    }
+
+    #[test]
+    fn test_bill_fixture() {
+        let bills = fixture();
+        assert_eq!(bills.len(), 3);
+
+        let trade = &bills[0];
+        assert_eq!(trade.bill_type, BillType::Trade);
+        assert_eq!(trade.sub_type, BillSubType::Buy);
+        assert_eq!(trade.inst_type, Some(InstType::Spot));
+        assert_eq!(trade.fee, Some("-0.000021955779".parse().unwrap()));
+        assert_eq!(trade.ts.timestamp_millis(), 1695033476167);
+        assert!(trade.fill_time.is_some());
+
+        let funding = &bills[1];
+        assert_eq!(funding.bill_type, BillType::FundingFee);
+        assert_eq!(funding.sub_type, BillSubType::FundingFeeExpense);
+        assert_eq!(funding.px, None);
+        assert_eq!(funding.fill_time, None);
+
+        let transfer = &bills[2];
+        assert_eq!(transfer.bill_type, BillType::Transfer);
+        assert_eq!(transfer.inst_type, None);
+        assert_eq!(transfer.from, "6");
+
+        // 空的产品类型序列化回空字符串
+        let json = serde_json::to_value(transfer).unwrap();
+        assert_eq!(json["instType"], "");
+        assert_eq!(json["type"], "1");
+    }
+
+    fn pager(limit: &str) -> BillsPager {
+        let params = BillsParams {
+            limit: Some(limit.to_string()),
+            ..Default::default()
+        };
+        BillsPager::new(
+            OkxClient::new(Config::test_default()),
+            BillsSource::Archive,
+            params,
+        )
+    }
+
+    #[test]
+    fn test_pager_uses_last_bill_id_as_cursor() {
+        let mut pager = pager("3");
+
+        let page = pager.advance(fixture());
+        assert_eq!(page.len(), 3);
+        assert_eq!(pager.cursor.as_deref(), Some("623940000000000002"));
+        assert!(!pager.done);
+
+        let page = pager.advance(fixture()[..1].to_vec());
+        assert_eq!(page.len(), 1);
+        assert!(pager.done);
+    }
+
+    #[test]
+    fn test_pager_stops_on_empty_page() {
+        let mut pager = pager("100");
+
+        assert!(pager.advance(Vec::new()).is_empty());
+        assert!(pager.done);
+        assert_eq!(pager.cursor, None);
+    }
+
+    #[test]
+    fn test_bill_table_row() {
+        let bills = fixture();
+        let row = bills[1].format_display(DisplayTz::Utc);
+
+        assert!(row.starts_with("2023-09-18 08:00:00.000 +00:00"));
+        assert!(row.contains("资金费"));
+        assert!(row.contains("173"));
+        assert!(row.contains("-0.52"));
+    }
+}
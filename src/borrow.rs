@@ -0,0 +1,375 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    client::OkxClient,
+    error::OkxError,
+    pager::TsCursor,
+    time::{opt_timestamp_ms, timestamp_ms, DisplayTz},
+    types::{decimal, opt_enum, ApiResponse, MarginMode},
+};
+
+/// 计息记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterestAccrued {
+    /// 借币类型：1 尊享借币，2 市场借币
+    #[serde(rename = "type", default)]
+    pub loan_type: String,
+    /// 借贷币种
+    pub ccy: String,
+    /// 产品ID，仅适用于币币杠杆
+    #[serde(rename = "instId", default)]
+    pub inst_id: String,
+    /// 保证金模式，跨币种保证金和组合保证金模式下为空
+    #[serde(rename = "mgnMode", default, with = "opt_enum")]
+    pub mgn_mode: Option<MarginMode>,
+    /// 利息
+    #[serde(default, with = "decimal")]
+    pub interest: Option<Decimal>,
+    /// 计息利率（小时）
+    #[serde(rename = "interestRate", default, with = "decimal")]
+    pub interest_rate: Option<Decimal>,
+    /// 计息负债
+    #[serde(default, with = "decimal")]
+    pub liab: Option<Decimal>,
+    /// 计息时间
+    #[serde(with = "timestamp_ms")]
+    pub ts: DateTime<Utc>,
+}
+
+/// 市场借币利率
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterestRate {
+    /// 币种
+    pub ccy: String,
+    /// 每小时借币利率
+    #[serde(rename = "interestRate", default, with = "decimal")]
+    pub interest_rate: Option<Decimal>,
+}
+
+/// 借币利率与限额
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterestLimits {
+    /// 当前负债（美元）
+    #[serde(default, with = "decimal")]
+    pub debt: Option<Decimal>,
+    /// 当前记息（美元），仅适用于市场借币
+    #[serde(default, with = "decimal")]
+    pub interest: Option<Decimal>,
+    /// 下次扣息时间
+    #[serde(rename = "nextInterestTime", default, with = "opt_timestamp_ms")]
+    pub next_interest_time: Option<DateTime<Utc>>,
+    /// 各币种明细
+    #[serde(default)]
+    pub records: Vec<InterestLimitRecord>,
+}
+
+/// 单个币种的借币利率与限额
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterestLimitRecord {
+    /// 借贷币种
+    pub ccy: String,
+    /// 日利率
+    #[serde(default, with = "decimal")]
+    pub rate: Option<Decimal>,
+    /// 母子账户维度借币限额
+    #[serde(rename = "loanQuota", default, with = "decimal")]
+    pub loan_quota: Option<Decimal>,
+    /// 母子账户维度剩余可借
+    #[serde(rename = "surplusLmt", default, with = "decimal")]
+    pub surplus_lmt: Option<Decimal>,
+    /// 当前账户已借额度
+    #[serde(rename = "usedLmt", default, with = "decimal")]
+    pub used_lmt: Option<Decimal>,
+    /// 已计未扣利息
+    #[serde(default, with = "decimal")]
+    pub interest: Option<Decimal>,
+}
+
+/// 手动借币/还币方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BorrowSide {
+    Borrow,
+    Repay,
+}
+
+/// 手动借币/还币请求（现货模式）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManualBorrowRepay {
+    pub ccy: String,
+    pub side: BorrowSide,
+    pub amt: Decimal,
+}
+
+/// 计息记录查询参数，`after`/`before` 为毫秒时间戳游标
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InterestAccruedParams {
+    /// 借币类型：1 尊享借币，2 市场借币
+    pub loan_type: Option<String>,
+    pub ccy: Option<String>,
+    pub inst_id: Option<String>,
+    pub mgn_mode: Option<MarginMode>,
+    pub after: Option<String>,
+    pub before: Option<String>,
+    pub limit: Option<String>,
+}
+
+impl InterestAccruedParams {
+    pub fn to_query_params(&self) -> HashMap<String, String> {
+        let mut params = HashMap::new();
+
+        if let Some(ref loan_type) = self.loan_type {
+            params.insert("type".to_string(), loan_type.clone());
+        }
+        if let Some(ref ccy) = self.ccy {
+            params.insert("ccy".to_string(), ccy.clone());
+        }
+        if let Some(ref inst_id) = self.inst_id {
+            params.insert("instId".to_string(), inst_id.clone());
+        }
+        if let Some(ref mgn_mode) = self.mgn_mode {
+            params.insert("mgnMode".to_string(), mgn_mode.to_string());
+        }
+        if let Some(ref after) = self.after {
+            params.insert("after".to_string(), after.clone());
+        }
+        if let Some(ref before) = self.before {
+            params.insert("before".to_string(), before.clone());
+        }
+        if let Some(ref limit) = self.limit {
+            params.insert("limit".to_string(), limit.clone());
+        }
+
+        params
+    }
+}
+
+impl OkxClient {
+    /// 获取计息记录
+    pub async fn get_interest_accrued(
+        &self,
+        params: &InterestAccruedParams,
+    ) -> Result<ApiResponse<InterestAccrued>, OkxError> {
+        let query_params = params.to_query_params();
+        self.get("/api/v5/account/interest-accrued", Some(&query_params))
+            .await
+    }
+
+    /// 获取用户当前市场借币利率，`ccy` 为空时返回所有币种
+    pub async fn get_interest_rate(
+        &self,
+        ccy: Option<&str>,
+    ) -> Result<ApiResponse<InterestRate>, OkxError> {
+        let mut query_params = HashMap::new();
+        if let Some(ccy) = ccy {
+            query_params.insert("ccy".to_string(), ccy.to_string());
+        }
+        self.get("/api/v5/account/interest-rate", Some(&query_params))
+            .await
+    }
+
+    /// 获取借币利率与限额
+    pub async fn get_interest_limits(
+        &self,
+        ccy: Option<&str>,
+    ) -> Result<ApiResponse<InterestLimits>, OkxError> {
+        let mut query_params = HashMap::new();
+        if let Some(ccy) = ccy {
+            query_params.insert("ccy".to_string(), ccy.to_string());
+        }
+        self.get("/api/v5/account/interest-limits", Some(&query_params))
+            .await
+    }
+
+    /// 现货模式手动借币/还币
+    pub async fn spot_manual_borrow_repay(
+        &self,
+        request: &ManualBorrowRepay,
+    ) -> Result<ApiResponse<ManualBorrowRepay>, OkxError> {
+        if request.amt <= Decimal::ZERO {
+            return Err(OkxError::Request(format!("无效的数量: {}", request.amt)));
+        }
+        self.post("/api/v5/account/spot-manual-borrow-repay", Some(request))
+            .await
+    }
+}
+
+/// 计息记录分页查询
+///
+/// 按 `ts` 游标向更早的记录翻页，游标规则见 [`TsCursor`]，
+/// 重复返回的边界记录按 `ccy + instId + mgnMode + ts` 去重。
+pub struct InterestAccruedPager {
+    client: OkxClient,
+    params: InterestAccruedParams,
+    cursor: TsCursor<(String, String, String)>,
+}
+
+impl InterestAccruedPager {
+    pub fn new(client: OkxClient, params: InterestAccruedParams) -> Self {
+        let cursor = TsCursor::new(
+            params.limit.as_deref(),
+            params.before.as_deref(),
+            params.after.as_deref(),
+        );
+        Self {
+            client,
+            params,
+            cursor,
+        }
+    }
+
+    /// 获取下一页记录，没有更多数据时返回 `None`
+    pub async fn next_page(&mut self) -> Result<Option<Vec<InterestAccrued>>, OkxError> {
+        while !self.cursor.is_done() {
+            let mut params = self.params.clone();
+            params.after = self.cursor.cursor().map(|cursor| cursor.to_string());
+            params.before = None;
+            params.limit = Some(self.cursor.limit().to_string());
+
+            let page = self.client.get_interest_accrued(&params).await?.data;
+            let records = self.cursor.advance(page, interest_key);
+            if !records.is_empty() {
+                return Ok(Some(records));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 获取所有剩余记录
+    pub async fn collect_all(mut self) -> Result<Vec<InterestAccrued>, OkxError> {
+        let mut all = Vec::new();
+        while let Some(records) = self.next_page().await? {
+            all.extend(records);
+        }
+        Ok(all)
+    }
+}
+
+/// 计息记录去重键
+fn interest_key(record: &InterestAccrued) -> ((String, String, String), i64) {
+    (
+        (
+            record.ccy.clone(),
+            record.inst_id.clone(),
+            record
+                .mgn_mode
+                .as_ref()
+                .map(|m| m.to_string())
+                .unwrap_or_default(),
+        ),
+        record.ts.timestamp_millis(),
+    )
+}
+
+impl OkxClient {
+    /// 创建计息记录分页查询
+    pub fn interest_accrued_pager(&self, params: InterestAccruedParams) -> InterestAccruedPager {
+        InterestAccruedPager::new(self.clone(), params)
+    }
+
+    /// 自动翻页获取所有计息记录
+    pub async fn get_interest_accrued_all(
+        &self,
+        params: &InterestAccruedParams,
+    ) -> Result<Vec<InterestAccrued>, OkxError> {
+        self.interest_accrued_pager(params.clone())
+            .collect_all()
+            .await
+    }
+}
+
+/// 按日期（`tz` 时区）和币种汇总利息
+pub fn daily_interest(
+    records: &[InterestAccrued],
+    tz: DisplayTz,
+) -> BTreeMap<(NaiveDate, String), Decimal> {
+    let mut daily = BTreeMap::new();
+    for record in records {
+        let date = tz.date(&record.ts);
+        *daily
+            .entry((date, record.ccy.clone()))
+            .or_insert(Decimal::ZERO) += record.interest.unwrap_or_default();
+    }
+    daily
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accrued(ccy: &str, ts: i64, interest: &str) -> InterestAccrued {
+        serde_json::from_value(serde_json::json!({
+            "type": "2", "ccy": ccy, "instId": "", "mgnMode": "",
+            "interest": interest, "interestRate": "0.0000049", "liab": "1000",
+            "ts": ts.to_string()
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_interest_accrued_deserialization() {
+        let record = accrued("USDT", 1597026383085, "0.0049");
+        assert_eq!(record.mgn_mode, None);
+        assert_eq!(record.interest, Some("0.0049".parse().unwrap()));
+        assert_eq!(record.ts.timestamp_millis(), 1597026383085);
+    }
+
+    #[test]
+    fn test_interest_key_dedups_by_ccy() {
+        let mut cursor = TsCursor::new(Some("2"), None, None);
+
+        let page = cursor.advance(
+            vec![accrued("BTC", 200, "1"), accrued("USDT", 100, "1")],
+            interest_key,
+        );
+        assert_eq!(page.len(), 2);
+
+        let page = cursor.advance(
+            vec![accrued("USDT", 100, "1"), accrued("ETH", 100, "1")],
+            interest_key,
+        );
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].ccy, "ETH");
+    }
+
+    #[test]
+    fn test_daily_interest() {
+        // 2024-03-10 23:00 UTC 在 +08:00 时区为 3月11日
+        let records = vec![
+            accrued("USDT", 1710111600000, "0.5"),
+            accrued("USDT", 1710072000000, "0.25"),
+            accrued("BTC", 1710108000000, "0.0001"),
+        ];
+
+        let utc = daily_interest(&records, DisplayTz::Utc);
+        let march_10 = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        assert_eq!(
+            utc.get(&(march_10, "USDT".to_string())),
+            Some(&"0.75".parse().unwrap())
+        );
+
+        let shanghai = daily_interest(&records, "+08:00".parse().unwrap());
+        let march_11 = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        assert_eq!(
+            shanghai.get(&(march_11, "USDT".to_string())),
+            Some(&"0.5".parse().unwrap())
+        );
+        assert_eq!(shanghai.len(), 3);
+    }
+
+    #[test]
+    fn test_manual_borrow_repay_serialization() {
+        let request = ManualBorrowRepay {
+            ccy: "USDT".to_string(),
+            side: BorrowSide::Repay,
+            amt: "100.5".parse().unwrap(),
+        };
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({"ccy": "USDT", "side": "repay", "amt": "100.5"})
+        );
+    }
+}
@@ -1,19 +1,36 @@
-use crate::{config::Config, rate_limiter::RateLimiter};
-use anyhow::{anyhow, Result};
+use crate::{
+    clock::{ServerClock, ServerTime},
+    config::Config,
+    error::OkxError,
+    rate_limiter::RateLimiter,
+    retry::RetryPolicy,
+    types::ApiResponse,
+};
+use chrono::{DateTime, Duration as ChronoDuration, TimeZone, Utc};
+use log::{info, warn};
 use reqwest::Client;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::time::delay_for;
 use hmac::{Hmac, Mac, NewMac};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use sha2::Sha256;
 use base64;
 
 /// 通用OKX API客户端
+///
+/// 克隆开销很小：连接池、限速器和时钟偏差在所有克隆之间共享，
+/// 应当在程序中只创建一次，然后按需克隆传递。
+#[derive(Clone)]
 pub struct OkxClient {
     client: Client,
-    config: Config,
-    rate_limiter: RateLimiter,
+    config: Arc<Config>,
+    rate_limiter: Arc<RateLimiter>,
+    retry_policy: RetryPolicy,
+    clock: Option<Arc<ServerClock>>,
 }
 
 impl OkxClient {
@@ -23,69 +40,273 @@ impl OkxClient {
             .timeout(Duration::from_secs(30))
             .build()
             .expect("Failed to create HTTP client");
+        let clock = if config.clock_sync {
+            Some(Arc::new(ServerClock::default()))
+        } else {
+            None
+        };
 
         Self {
             client,
-            config,
-            rate_limiter: RateLimiter::default(),
+            config: Arc::new(config),
+            rate_limiter: Arc::new(RateLimiter::default()),
+            retry_policy: RetryPolicy::default(),
+            clock,
         }
     }
 
+    /// 客户端配置
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// 设置限速器
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Arc::new(rate_limiter);
+        self
+    }
+
+    /// 设置重试策略
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// 启用服务器时间同步，每隔 `refresh_interval` 重新测量一次时钟偏差
+    pub fn with_clock_sync(mut self, refresh_interval: Duration) -> Self {
+        self.clock = Some(Arc::new(ServerClock::new(refresh_interval)));
+        self
+    }
+
+    /// 最近一次测得的时钟偏差（服务器时间 - 本地时间）
+    ///
+    /// 未启用时间同步或从未测量过时返回 `None`。收到 50102 后偏差被标记为待重新同步，
+    /// 但仍返回上一次的测量值。
+    pub fn clock_skew(&self) -> Option<ChronoDuration> {
+        self.clock.as_ref().and_then(|clock| clock.last_offset())
+    }
+
+    /// 立即与服务器同步时间，返回测得的时钟偏差
+    pub async fn sync_server_time(&self) -> Result<ChronoDuration, OkxError> {
+        let clock = self
+            .clock
+            .as_ref()
+            .ok_or_else(|| OkxError::Request("未启用服务器时间同步".to_string()))?;
+
+        let local_sent = Utc::now();
+        let server_time = self.fetch_server_time().await?;
+        let local_received = Utc::now();
+
+        let offset = clock.update(local_sent, server_time, local_received);
+        info!("服务器时间同步完成，时钟偏差 {}ms", offset.num_milliseconds());
+        Ok(offset)
+    }
+
+    /// 查询服务器时间（公共接口，无需签名）
+    pub async fn fetch_server_time(&self) -> Result<DateTime<Utc>, OkxError> {
+        const ENDPOINT: &str = "/api/v5/public/time";
+
+        let url = format!("{}{}", self.config.base_url, ENDPOINT);
+        let network_error = |source| OkxError::Network {
+            path: ENDPOINT.to_string(),
+            source,
+        };
+        let response = self.client.get(&url).send().await.map_err(network_error)?;
+        let status = response.status();
+        let response_text = response.text().await.map_err(network_error)?;
+
+        if !status.is_success() {
+            return Err(OkxError::http(ENDPOINT, status.as_u16(), &response_text));
+        }
+
+        let decode_error = |source| OkxError::Decode {
+            path: ENDPOINT.to_string(),
+            source,
+        };
+        let json_value: Value = serde_json::from_str(&response_text).map_err(decode_error)?;
+        OkxError::check_api(ENDPOINT, status.as_u16(), &json_value)?;
+        let response: ApiResponse<ServerTime> =
+            serde_json::from_value(json_value).map_err(decode_error)?;
+
+        response
+            .data
+            .first()
+            .and_then(|time| time.ts.parse::<i64>().ok())
+            .and_then(|ts| Utc.timestamp_millis_opt(ts).single())
+            .ok_or_else(|| OkxError::Request("服务器时间格式无效".to_string()))
+    }
+
     /// 执行GET请求
-    pub async fn get<T>(&self, endpoint: &str, params: Option<&HashMap<String, String>>) -> Result<T>
+    pub async fn get<T>(
+        &self,
+        endpoint: &str,
+        params: Option<&HashMap<String, String>>,
+    ) -> Result<T, OkxError>
     where
         T: DeserializeOwned,
     {
-        self.request::<T>("GET", endpoint, params, None::<&()>).await
+        self.request::<T>("GET", endpoint, params, None::<&()>, false)
+            .await
     }
 
     /// 执行POST请求
-    pub async fn post<T>(&self, endpoint: &str, body: Option<&impl Serialize>) -> Result<T>
+    ///
+    /// 请求体只序列化一次，签名与实际发送的是同一份字节。
+    pub async fn post<T>(&self, endpoint: &str, body: Option<&impl Serialize>) -> Result<T, OkxError>
     where
         T: DeserializeOwned,
     {
-        self.request::<T>("POST", endpoint, None, body).await
+        self.request::<T>("POST", endpoint, None, body, false).await
+    }
+
+    /// 执行返回逐条结果的POST请求（下单、撤单、改单等）
+    ///
+    /// 业务码为1（全部失败）或2（部分失败）且 `data[]` 带有 `sCode` 时不视为错误，
+    /// 由调用方逐条检查 `sCode/sMsg`；其他非0业务码仍返回 [`OkxError::Api`]。
+    pub async fn post_items<T>(
+        &self,
+        endpoint: &str,
+        body: Option<&impl Serialize>,
+    ) -> Result<T, OkxError>
+    where
+        T: DeserializeOwned,
+    {
+        self.request::<T>("POST", endpoint, None, body, true).await
     }
 
     /// 通用请求方法
+    ///
+    /// OKX v5 REST 只使用 GET（查询参数）和 POST（JSON 请求体）两种方法。
+    /// 失败时按 `retry_policy` 重试，GET 视为幂等请求，POST 只在确定未被处理时重试。
     async fn request<T>(
         &self,
         method: &str,
         endpoint: &str,
         params: Option<&HashMap<String, String>>,
         body: Option<&impl Serialize>,
-    ) -> Result<T>
+        item_results: bool,
+    ) -> Result<T, OkxError>
     where
         T: DeserializeOwned,
     {
+        // 构建请求路径，URL和签名共用同一个查询字符串
+        let request_path = build_request_path(endpoint, params);
+
+        // 序列化请求体，签名和发送使用同一个字符串
+        let body = match body {
+            Some(body) => serde_json::to_string(body)
+                .map_err(|e| OkxError::Request(format!("请求体序列化失败: {}", e)))?,
+            None => String::new(),
+        };
+
+        // 交易接口按产品限速，从请求体中取出 instId
+        let inst_ids = inst_ids_from_body(&body);
+
+        let idempotent = method == "GET";
+        let mut attempt = 1;
+        let mut resynced = false;
+        let json_value = loop {
+            match self
+                .send(
+                    method,
+                    endpoint,
+                    &request_path,
+                    &body,
+                    &inst_ids,
+                    item_results,
+                )
+                .await
+            {
+                Ok(value) => break value,
+                Err(err) => {
+                    if self.resync_on_expired(&err, &mut resynced) {
+                        warn!(
+                            "{} {} 时间戳过期，重新同步服务器时间后重试: {}",
+                            method, endpoint, err
+                        );
+                        continue;
+                    }
+
+                    if !self.retry_policy.should_retry(&err, idempotent, attempt) {
+                        if attempt > 1 {
+                            warn!("{} {} 第{}次请求失败，放弃重试: {}", method, endpoint, attempt, err);
+                        }
+                        return Err(err);
+                    }
+
+                    let delay = self.retry_policy.backoff(attempt);
+                    warn!(
+                        "{} {} 第{}次请求失败，{}ms后重试: {}",
+                        method,
+                        endpoint,
+                        attempt,
+                        delay.as_millis(),
+                        err
+                    );
+                    delay_for(delay).await;
+                    attempt += 1;
+                }
+            }
+        };
+
+        // 解析响应数据
+        serde_json::from_value(json_value).map_err(|source| OkxError::Decode {
+            path: endpoint.to_string(),
+            source,
+        })
+    }
+
+    /// 处理时间戳过期（50102）：标记时钟偏差失效，返回是否应重新同步后立即重试
+    ///
+    /// 50102 的请求未被交易所处理，非幂等请求也可以安全重试。只在启用时间同步时重试，
+    /// 且每次请求只重试一次；重试时 `send` 会先重新同步再重新签名。
+    fn resync_on_expired(&self, err: &OkxError, resynced: &mut bool) -> bool {
+        let clock = match &self.clock {
+            Some(clock) if err.is_timestamp_expired() => clock,
+            _ => return false,
+        };
+
+        clock.invalidate();
+        if *resynced {
+            return false;
+        }
+        *resynced = true;
+        true
+    }
+
+    /// 签名并发送一次请求，返回业务码为0的响应JSON
+    ///
+    /// `item_results` 为true时，业务码为1或2的逐条结果也一并返回。
+    async fn send(
+        &self,
+        method: &str,
+        endpoint: &str,
+        request_path: &str,
+        body: &str,
+        inst_ids: &[String],
+        item_results: bool,
+    ) -> Result<Value, OkxError> {
         // 等待限速
-        self.rate_limiter.wait_if_needed(&self.config.api_key)?;
+        self.rate_limiter
+            .acquire(&self.config.api_key, endpoint, inst_ids)
+            .await;
 
-        // 构建URL
-        let url = format!("{}{}", self.config.base_url, endpoint);
+        let url = format!("{}{}", self.config.base_url, request_path);
 
         // 构建请求
         let mut request = match method {
             "GET" => self.client.get(&url),
             "POST" => self.client.post(&url),
-            _ => return Err(anyhow!("不支持的HTTP方法: {}", method)),
+            _ => return Err(OkxError::Request(format!("不支持的HTTP方法: {}", method))),
         };
 
-        // 添加查询参数
-        if let Some(params) = params {
-            for (key, value) in params {
-                request = request.query(&[(key, value)]);
-            }
-        }
-
-        // 添加请求体
-        if let Some(body) = body {
-            request = request.json(body);
+        if !body.is_empty() {
+            request = request.body(body.to_string());
         }
 
         // 生成认证头
         let timestamp = self.get_timestamp().await?;
-        let signature = self.generate_signature(method, endpoint, params, &timestamp)?;
+        let signature = self.generate_signature(&timestamp, method, request_path, body)?;
 
         // 添加请求头
         request = request
@@ -100,78 +321,141 @@ impl OkxClient {
         }
 
         // 发送请求
-        let response = request.send().await?;
+        let network_error = |source| OkxError::Network {
+            path: endpoint.to_string(),
+            source,
+        };
+        let response = request.send().await.map_err(network_error)?;
+        let status = response.status();
 
-        if !response.status().is_success() {
-            let status = response.status();
+        if !status.is_success() {
             let error_text = response.text().await.unwrap_or_else(|_| "无法读取错误信息".to_string());
-            return Err(anyhow!("HTTP错误: {} - {}", status, error_text));
+            return Err(OkxError::http(endpoint, status.as_u16(), &error_text));
         }
 
         // 解析响应
-        let response_text = response.text().await?;
-        let json_value: Value = serde_json::from_str(&response_text)?;
+        let response_text = response.text().await.map_err(network_error)?;
+        let json_value: Value =
+            serde_json::from_str(&response_text).map_err(|source| OkxError::Decode {
+                path: endpoint.to_string(),
+                source,
+            })?;
 
         // 检查API响应状态
-        if let Some(code) = json_value["code"].as_str() {
-            if code != "0" {
-                let msg = json_value["msg"].as_str().unwrap_or("未知错误");
-                return Err(anyhow!("API错误: {} - {}", code, msg));
-            }
+        if !(item_results && OkxError::has_item_results(&json_value)) {
+            OkxError::check_api(endpoint, status.as_u16(), &json_value)?;
         }
 
-        // 解析响应数据
-        let api_response: T = serde_json::from_value(json_value)?;
-        Ok(api_response)
+        Ok(json_value)
     }
 
     /// 生成签名
     fn generate_signature(
         &self,
-        method: &str,
-        endpoint: &str,
-        params: Option<&HashMap<String, String>>,
         timestamp: &str,
-    ) -> Result<String> {
-        // 构建签名字符串
-        let mut sign_string = timestamp.to_string() + method + endpoint;
-
-        // 添加查询参数
-        if let Some(params) = params {
-            if !params.is_empty() {
-                let mut sorted_params: Vec<_> = params.iter().collect();
-                sorted_params.sort_by_key(|&(k, _)| k);
-
-                let param_string = sorted_params
-                    .iter()
-                    .map(|(k, v)| format!("{}={}", k, v))
-                    .collect::<Vec<_>>()
-                    .join("&");
-
-                sign_string.push_str("?");
-                sign_string.push_str(&param_string);
+        method: &str,
+        request_path: &str,
+        body: &str,
+    ) -> Result<String, OkxError> {
+        sign(&self.config.secret_key, timestamp, method, request_path, body)
+    }
+
+    /// 获取时间戳
+    ///
+    /// 启用时间同步时使用校正后的服务器时间，同步失败则沿用上一次的偏差。
+    async fn get_timestamp(&self) -> Result<String, OkxError> {
+        let now = match &self.clock {
+            Some(clock) => {
+                if clock.needs_sync() {
+                    if let Err(e) = self.sync_server_time().await {
+                        warn!("服务器时间同步失败，使用上一次的时钟偏差: {}", e);
+                    }
+                }
+                clock.now()
             }
-        }
+            None => Utc::now(),
+        };
 
-        // 使用HMAC-SHA256生成签名
-        let mut mac = Hmac::<Sha256>::new_from_slice(self.config.secret_key.as_bytes())
-            .map_err(|e| anyhow!("HMAC初始化失败: {}", e))?;
+        // 使用ISO 8601格式的时间戳，如：2020-12-08T09:08:57.715Z
+        Ok(now.format("%Y-%m-%dT%H:%M:%S.%3fZ").to_string())
+    }
+}
 
-        mac.update(sign_string.as_bytes());
-        let result = mac.finalize();
+/// 查询字符串中需要转义的字符：除 RFC 3986 非保留字符 (`A-Z a-z 0-9 - _ . ~`) 以外全部转义
+const QUERY_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// 构建规范化的查询字符串
+///
+/// 参数按键名排序并做百分号编码，结果既用于请求URL也用于签名，保证两者逐字节一致。
+pub fn build_query_string(params: &HashMap<String, String>) -> String {
+    let mut sorted_params: Vec<_> = params.iter().collect();
+    sorted_params.sort();
+
+    sorted_params
+        .iter()
+        .map(|(k, v)| {
+            format!(
+                "{}={}",
+                utf8_percent_encode(k, QUERY_ENCODE_SET),
+                utf8_percent_encode(v, QUERY_ENCODE_SET)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
 
-        Ok(base64::encode(result.into_bytes()))
+/// 构建请求路径（endpoint + 规范化查询字符串）
+fn build_request_path(endpoint: &str, params: Option<&HashMap<String, String>>) -> String {
+    match params {
+        Some(params) if !params.is_empty() => {
+            format!("{}?{}", endpoint, build_query_string(params))
+        }
+        _ => endpoint.to_string(),
     }
+}
 
-    /// 获取时间戳
-    async fn get_timestamp(&self) -> Result<String> {
-        // 使用ISO 8601格式的时间戳，如：2020-12-08T09:08:57.715Z
-        use chrono::Utc;
-        let utc_now = Utc::now();
-        // 格式化为ISO 8601格式，包含毫秒
-        let timestamp = utc_now.format("%Y-%m-%dT%H:%M:%S.%3fZ").to_string();
-        Ok(timestamp)
-    }
+/// 从JSON请求体（对象或批量数组）中提取 instId
+fn inst_ids_from_body(body: &str) -> Vec<String> {
+    let value: Value = match serde_json::from_str(body) {
+        Ok(value) => value,
+        Err(_) => return Vec::new(),
+    };
+
+    let items = match value {
+        Value::Array(items) => items,
+        item => vec![item],
+    };
+    items
+        .iter()
+        .filter_map(|item| item["instId"].as_str().map(|s| s.to_string()))
+        .collect()
+}
+
+/// 按OKX规则计算签名
+///
+/// 签名原文为 `timestamp + method + requestPath + body`，使用 HMAC-SHA256 后做 Base64 编码。
+/// GET 请求的 body 为空字符串，requestPath 包含查询字符串。
+pub fn sign(
+    secret_key: &str,
+    timestamp: &str,
+    method: &str,
+    request_path: &str,
+    body: &str,
+) -> Result<String, OkxError> {
+    let sign_string = format!("{}{}{}{}", timestamp, method, request_path, body);
+
+    // 使用HMAC-SHA256生成签名
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret_key.as_bytes())
+        .map_err(|e| OkxError::Request(format!("HMAC初始化失败: {}", e)))?;
+
+    mac.update(sign_string.as_bytes());
+    let result = mac.finalize();
+
+    Ok(base64::encode(result.into_bytes()))
 }
 
 /// 创建默认的API客户端
@@ -182,38 +466,204 @@ pub fn create_client(config: Config) -> OkxClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_client_creation() {
-        let config = Config {
-            api_key: "test_key".to_string(),
-            secret_key: "test_secret".to_string(),
-            passphrase: "test_passphrase".to_string(),
-            base_url: "https://www.okx.com".to_string(),
-            is_sandbox: false,
-        };
+        let config = Config::test_default();
 
         let client = OkxClient::new(config);
         assert_eq!(client.config.api_key, "test_key");
     }
 
+    #[test]
+    fn test_client_clone_shares_state() {
+        let client = OkxClient::new(Config::test_default());
+        let cloned = client.clone();
+
+        assert!(Arc::ptr_eq(&client.rate_limiter, &cloned.rate_limiter));
+        assert!(Arc::ptr_eq(&client.config, &cloned.config));
+    }
+
     #[test]
     fn test_signature_generation() {
-        let config = Config {
-            api_key: "test_key".to_string(),
-            secret_key: "test_secret".to_string(),
-            passphrase: "test_passphrase".to_string(),
-            base_url: "https://www.okx.com".to_string(),
-            is_sandbox: false,
-        };
+        let config = Config::test_default();
 
         let client = OkxClient::new(config);
         let timestamp = "2020-12-08T09:08:57.715Z";
         let method = "GET";
         let endpoint = "/api/v5/account/positions";
-        let params = None;
 
-        let signature = client.generate_signature(method, endpoint, params, timestamp).unwrap();
+        let signature = client.generate_signature(timestamp, method, endpoint, "").unwrap();
         assert!(!signature.is_empty());
     }
+
+    // 以下向量使用OKX文档示例中的 SecretKey 和时间戳
+    const DOC_SECRET: &str = "22582BD0CFF14C41EDBF1AB98506286D";
+    const DOC_TIMESTAMP: &str = "2020-12-08T09:08:57.715Z";
+
+    #[test]
+    fn test_sign_get_with_query() {
+        let signature = sign(
+            DOC_SECRET,
+            DOC_TIMESTAMP,
+            "GET",
+            "/api/v5/account/balance?ccy=BTC",
+            "",
+        )
+        .unwrap();
+        assert_eq!(signature, "HiZhvSfMtWJA3uUIVXV3a/bSXNPCWvYFXoGCVS8V4zY=");
+    }
+
+    #[test]
+    fn test_sign_post_with_body() {
+        let body = r#"{"instId":"BTC-USDT","tdMode":"cash","side":"buy","ordType":"market","sz":"100"}"#;
+        let signature = sign(DOC_SECRET, DOC_TIMESTAMP, "POST", "/api/v5/trade/order", body).unwrap();
+        assert_eq!(signature, "gLoEHvSwOThUNff+Hv0T7J9JHtjIwa/gKHKIkc4vvhM=");
+    }
+
+    #[test]
+    fn test_sign_post_without_body() {
+        let signature = sign(
+            DOC_SECRET,
+            DOC_TIMESTAMP,
+            "POST",
+            "/api/v5/account/set-position-mode",
+            "",
+        )
+        .unwrap();
+        assert_eq!(signature, "nj0kdCCa3iDL/9Gqer892vDNRM1lf8jmtUQMSxx6KSw=");
+    }
+
+    #[test]
+    fn test_sign_serialized_body_matches_raw() {
+        #[derive(Serialize)]
+        struct Order {
+            #[serde(rename = "instId")]
+            inst_id: &'static str,
+            #[serde(rename = "tdMode")]
+            td_mode: &'static str,
+            side: &'static str,
+            #[serde(rename = "ordType")]
+            ord_type: &'static str,
+            sz: &'static str,
+        }
+
+        let body = serde_json::to_string(&Order {
+            inst_id: "BTC-USDT",
+            td_mode: "cash",
+            side: "buy",
+            ord_type: "market",
+            sz: "100",
+        })
+        .unwrap();
+        let signature = sign(DOC_SECRET, DOC_TIMESTAMP, "POST", "/api/v5/trade/order", &body).unwrap();
+        assert_eq!(signature, "gLoEHvSwOThUNff+Hv0T7J9JHtjIwa/gKHKIkc4vvhM=");
+    }
+
+    #[test]
+    fn test_build_request_path_sorts_params() {
+        let mut params = HashMap::new();
+        params.insert("limit".to_string(), "10".to_string());
+        params.insert("instType".to_string(), "SWAP".to_string());
+
+        assert_eq!(
+            build_request_path("/api/v5/account/positions-history", Some(&params)),
+            "/api/v5/account/positions-history?instType=SWAP&limit=10"
+        );
+        assert_eq!(build_request_path("/api/v5/account/balance", None), "/api/v5/account/balance");
+    }
+
+    #[test]
+    fn test_build_query_string_encodes_reserved_chars() {
+        let mut params = HashMap::new();
+        params.insert("instId".to_string(), "BTC-USDT".to_string());
+        params.insert("ccy".to_string(), "BTC,ETH".to_string());
+        params.insert("tag".to_string(), "a b+c&d=e".to_string());
+
+        assert_eq!(
+            build_query_string(&params),
+            "ccy=BTC%2CETH&instId=BTC-USDT&tag=a%20b%2Bc%26d%3De"
+        );
+    }
+
+    #[test]
+    fn test_inst_ids_from_body() {
+        assert_eq!(
+            inst_ids_from_body(r#"{"instId":"BTC-USDT","sz":"1"}"#),
+            vec!["BTC-USDT".to_string()]
+        );
+        assert_eq!(
+            inst_ids_from_body(r#"[{"instId":"BTC-USDT"},{"instId":"ETH-USDT"}]"#),
+            vec!["BTC-USDT".to_string(), "ETH-USDT".to_string()]
+        );
+        assert!(inst_ids_from_body("").is_empty());
+        assert!(inst_ids_from_body(r#"{"posMode":"net_mode"}"#).is_empty());
+    }
+
+    #[test]
+    fn test_resync_on_expired_retries_once() {
+        let expired = OkxError::check_api(
+            "/api/v5/trade/order",
+            200,
+            &serde_json::json!({"code": "50102", "msg": "Timestamp request expired", "data": []}),
+        )
+        .unwrap_err();
+
+        // 未启用时间同步时不重试
+        let client = OkxClient::new(Config::test_default());
+        assert!(!client.resync_on_expired(&expired, &mut false));
+
+        let client = OkxClient::new(Config::test_default()).with_clock_sync(Duration::from_secs(60));
+        let clock = client.clock.as_ref().unwrap();
+        let now = Utc::now();
+        clock.update(now, now + ChronoDuration::seconds(2), now);
+
+        let mut resynced = false;
+        assert!(client.resync_on_expired(&expired, &mut resynced));
+        assert!(clock.needs_sync());
+        // 重新同步前仍报告上一次的偏差
+        assert_eq!(client.clock_skew(), Some(ChronoDuration::seconds(2)));
+        assert!(!client.resync_on_expired(&expired, &mut resynced));
+
+        let other = OkxError::Request("other".to_string());
+        assert!(!client.resync_on_expired(&other, &mut false));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_url_query_matches_signed_query(
+            params in proptest::collection::hash_map(".{1,12}", ".{0,12}", 0..6)
+        ) {
+            let request_path = build_request_path("/api/v5/account/positions-history", Some(&params));
+            let url = reqwest::Url::parse(&format!("https://www.okx.com{}", request_path)).unwrap();
+
+            // 发送的URL不会被再次编码，与签名原文中的requestPath完全一致
+            let sent_path = match url.query() {
+                Some(query) => format!("{}?{}", url.path(), query),
+                None => url.path().to_string(),
+            };
+            prop_assert_eq!(&sent_path, &request_path);
+
+            // 服务端解码后得到的参数与输入一致
+            let decoded: HashMap<String, String> = url.query_pairs().into_owned().collect();
+            prop_assert_eq!(decoded, params);
+        }
+
+        #[test]
+        fn prop_query_string_is_deterministic(
+            params in proptest::collection::hash_map("[a-zA-Z]{1,8}", "[ -~]{0,8}", 0..6)
+        ) {
+            // 以相反顺序插入另一个HashMap，迭代顺序不同但查询字符串必须相同
+            let mut entries: Vec<_> = params.iter().collect();
+            entries.sort();
+            let reversed: HashMap<String, String> = entries
+                .into_iter()
+                .rev()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+
+            prop_assert_eq!(build_query_string(&params), build_query_string(&reversed));
+        }
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,147 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 服务器时间（`/api/v5/public/time`）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServerTime {
+    /// 服务器时间，Unix毫秒时间戳
+    pub ts: String,
+}
+
+/// 服务器时间同步
+///
+/// 记录本地时钟与OKX服务器时钟的偏差（毫秒），签名时间戳使用 `本地时间 + 偏差`。
+/// 偏差在超过 `refresh_interval` 后由客户端重新测量。
+#[derive(Debug)]
+pub struct ServerClock {
+    offset_ms: AtomicI64,
+    measured: AtomicBool,
+    synced_at: Mutex<Option<Instant>>,
+    refresh_interval: Duration,
+}
+
+impl ServerClock {
+    pub fn new(refresh_interval: Duration) -> Self {
+        Self {
+            offset_ms: AtomicI64::new(0),
+            measured: AtomicBool::new(false),
+            synced_at: Mutex::new(None),
+            refresh_interval,
+        }
+    }
+
+    /// 当前测得的偏差（服务器时间 - 本地时间），正值表示本地时钟偏慢
+    pub fn offset(&self) -> ChronoDuration {
+        ChronoDuration::milliseconds(self.offset_ms.load(Ordering::Relaxed))
+    }
+
+    /// 最近一次测得的偏差，`invalidate()` 后仍保留，从未测量过时返回 `None`
+    pub fn last_offset(&self) -> Option<ChronoDuration> {
+        if self.measured.load(Ordering::Relaxed) {
+            Some(self.offset())
+        } else {
+            None
+        }
+    }
+
+    /// 偏差是否仍然有效（已同步且未被 `invalidate()` 标记失效）
+    pub fn is_synced(&self) -> bool {
+        self.synced_at.lock().unwrap().is_some()
+    }
+
+    /// 是否需要重新同步
+    pub fn needs_sync(&self) -> bool {
+        match *self.synced_at.lock().unwrap() {
+            Some(synced_at) => synced_at.elapsed() >= self.refresh_interval,
+            None => true,
+        }
+    }
+
+    /// 根据一次服务器时间查询更新偏差
+    ///
+    /// 假设网络往返对称，服务器时间对应本地发送与接收时间的中点。
+    pub fn update(
+        &self,
+        local_sent: DateTime<Utc>,
+        server_time: DateTime<Utc>,
+        local_received: DateTime<Utc>,
+    ) -> ChronoDuration {
+        let round_trip = local_received - local_sent;
+        let local_mid = local_sent + round_trip / 2;
+        let offset = server_time - local_mid;
+
+        self.offset_ms.store(offset.num_milliseconds(), Ordering::Relaxed);
+        self.measured.store(true, Ordering::Relaxed);
+        *self.synced_at.lock().unwrap() = Some(Instant::now());
+        offset
+    }
+
+    /// 标记偏差失效，下一次请求前重新同步（如收到 50102 时间戳过期）
+    ///
+    /// 重新同步之前仍使用上一次测得的偏差。
+    pub fn invalidate(&self) {
+        *self.synced_at.lock().unwrap() = None;
+    }
+
+    /// 按服务器时钟校正后的当前时间
+    pub fn now(&self) -> DateTime<Utc> {
+        Utc::now() + self.offset()
+    }
+}
+
+impl Default for ServerClock {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(300))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_update_uses_round_trip_midpoint() {
+        let clock = ServerClock::default();
+        let sent = Utc.timestamp_millis_opt(1_597_026_383_000).unwrap();
+        let received = sent + ChronoDuration::milliseconds(200);
+        // 服务器比本地快 1500ms（相对于往返中点）
+        let server = sent + ChronoDuration::milliseconds(100 + 1500);
+
+        let offset = clock.update(sent, server, received);
+        assert_eq!(offset, ChronoDuration::milliseconds(1500));
+        assert_eq!(clock.offset(), ChronoDuration::milliseconds(1500));
+    }
+
+    #[test]
+    fn test_needs_sync_and_invalidate() {
+        let clock = ServerClock::new(Duration::from_secs(60));
+        assert!(clock.needs_sync());
+        assert!(!clock.is_synced());
+
+        assert_eq!(clock.last_offset(), None);
+
+        let now = Utc::now();
+        clock.update(now, now + ChronoDuration::milliseconds(800), now);
+        assert!(!clock.needs_sync());
+        assert!(clock.is_synced());
+
+        clock.invalidate();
+        assert!(clock.needs_sync());
+        assert!(!clock.is_synced());
+        assert_eq!(clock.last_offset(), Some(ChronoDuration::milliseconds(800)));
+    }
+
+    #[test]
+    fn test_now_applies_offset() {
+        let clock = ServerClock::default();
+        let now = Utc::now();
+        clock.update(now, now - ChronoDuration::seconds(40), now);
+
+        let drift = Utc::now() - clock.now();
+        assert!(drift >= ChronoDuration::seconds(39) && drift <= ChronoDuration::seconds(41));
+    }
+}
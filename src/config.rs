@@ -1,5 +1,92 @@
 use anyhow::{anyhow, Result};
 use std::env;
+use std::fmt;
+use std::str::FromStr;
+
+/// OKX站点区域，决定REST和WebSocket域名
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    /// 全球站 www.okx.com
+    Global,
+    /// 全球站备用域名 app.okx.com
+    App,
+    /// AWS 专线 aws.okx.com
+    Aws,
+    /// 欧洲经济区 eea.okx.com
+    Eea,
+    /// 美国站 us.okx.com
+    Us,
+}
+
+/// 交易环境
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Environment {
+    /// 实盘
+    Live,
+    /// 模拟盘，请求需携带 `x-simulated-trading: 1`
+    Demo,
+}
+
+impl Region {
+    /// REST API 地址
+    pub fn rest_url(&self) -> &'static str {
+        match self {
+            Region::Global => "https://www.okx.com",
+            Region::App => "https://app.okx.com",
+            Region::Aws => "https://aws.okx.com",
+            Region::Eea => "https://eea.okx.com",
+            Region::Us => "https://us.okx.com",
+        }
+    }
+
+    /// WebSocket 地址（不含 `/ws/v5/...` 路径）
+    pub fn ws_url(&self, environment: Environment) -> &'static str {
+        match (self, environment) {
+            (Region::Global, Environment::Live) | (Region::App, Environment::Live) => {
+                "wss://ws.okx.com:8443"
+            }
+            (Region::Aws, Environment::Live) => "wss://wsaws.okx.com:8443",
+            (Region::Eea, Environment::Live) => "wss://wseea.okx.com:8443",
+            (Region::Us, Environment::Live) => "wss://wsus.okx.com:8443",
+            (Region::Global, Environment::Demo)
+            | (Region::App, Environment::Demo)
+            | (Region::Aws, Environment::Demo) => "wss://wspap.okx.com:8443",
+            (Region::Eea, Environment::Demo) => "wss://wseeapap.okx.com:8443",
+            (Region::Us, Environment::Demo) => "wss://wsuspap.okx.com:8443",
+        }
+    }
+}
+
+impl FromStr for Region {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "global" | "www" => Ok(Region::Global),
+            "app" => Ok(Region::App),
+            "aws" => Ok(Region::Aws),
+            "eea" => Ok(Region::Eea),
+            "us" => Ok(Region::Us),
+            other => Err(anyhow!(
+                "无效的区域: {} (可选: global, app, aws, eea, us)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Region::Global => "global",
+            Region::App => "app",
+            Region::Aws => "aws",
+            Region::Eea => "eea",
+            Region::Us => "us",
+        };
+        write!(f, "{}", name)
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -8,32 +95,146 @@ pub struct Config {
     pub passphrase: String,
     pub base_url: String,
     pub is_sandbox: bool,
+    /// 是否使用服务器时间校正签名时间戳
+    pub clock_sync: bool,
+    /// 站点区域
+    pub region: Region,
+    /// 公共频道 WebSocket 地址
+    ///
+    /// 本库不建立 WebSocket 连接，两个地址仅按区域和环境拼好供调用方使用。
+    pub ws_public_url: String,
+    /// 私有频道 WebSocket 地址
+    pub ws_private_url: String,
 }
 
 impl Config {
+    /// 根据区域和环境创建配置
+    pub fn new(
+        api_key: impl Into<String>,
+        secret_key: impl Into<String>,
+        passphrase: impl Into<String>,
+        region: Region,
+        environment: Environment,
+    ) -> Self {
+        let ws_url = region.ws_url(environment);
+
+        Config {
+            api_key: api_key.into(),
+            secret_key: secret_key.into(),
+            passphrase: passphrase.into(),
+            base_url: region.rest_url().to_string(),
+            is_sandbox: environment == Environment::Demo,
+            clock_sync: false,
+            region,
+            ws_public_url: format!("{}/ws/v5/public", ws_url),
+            ws_private_url: format!("{}/ws/v5/private", ws_url),
+        }
+    }
+
+    /// 测试用的实盘全球站配置
+    #[cfg(test)]
+    pub fn test_default() -> Self {
+        Self::new(
+            "test_key",
+            "test_secret",
+            "test_passphrase",
+            Region::Global,
+            Environment::Live,
+        )
+    }
+
     pub fn from_env() -> Result<Self> {
         let api_key = env::var("OKX_API_KEY")
             .map_err(|_| anyhow!("缺少环境变量 OKX_API_KEY"))?;
-        
+
         let secret_key = env::var("OKX_SECRET_KEY")
             .map_err(|_| anyhow!("缺少环境变量 OKX_SECRET_KEY"))?;
-        
+
         let passphrase = env::var("OKX_PASSPHRASE")
             .map_err(|_| anyhow!("缺少环境变量 OKX_PASSPHRASE"))?;
-        
-        let is_sandbox = env::var("OKX_SANDBOX")
-            .unwrap_or_else(|_| "false".to_string())
-            .parse()
-            .unwrap_or(false);
-        
-        let base_url = "https://www.okx.com".to_string();
-
-        Ok(Config {
-            api_key,
-            secret_key,
-            passphrase,
-            base_url,
-            is_sandbox,
-        })
-    }
-} 
\ No newline at end of file
+
+        // 模拟盘密钥在实盘请求会返回 50101，因此无法识别的取值直接报错而不是当作 false
+        let is_sandbox = bool_from_env("OKX_SANDBOX")?;
+        let clock_sync = bool_from_env("OKX_CLOCK_SYNC")?;
+
+        let region = match env::var("OKX_REGION") {
+            Ok(region) => region.parse()?,
+            Err(_) => Region::Global,
+        };
+        let environment = if is_sandbox {
+            Environment::Demo
+        } else {
+            Environment::Live
+        };
+
+        let mut config = Config::new(api_key, secret_key, passphrase, region, environment);
+        config.clock_sync = clock_sync;
+
+        // 测试或本地mock服务可以覆盖REST地址
+        if let Ok(base_url) = env::var("OKX_BASE_URL") {
+            config.base_url = base_url.trim_end_matches('/').to_string();
+        }
+
+        Ok(config)
+    }
+
+    /// 当前交易环境
+    pub fn environment(&self) -> Environment {
+        if self.is_sandbox {
+            Environment::Demo
+        } else {
+            Environment::Live
+        }
+    }
+}
+
+/// 解析布尔型环境变量，未设置时为 false
+fn bool_from_env(name: &str) -> Result<bool> {
+    match env::var(name) {
+        Ok(value) => parse_bool(&value)
+            .ok_or_else(|| anyhow!("环境变量 {} 的值无效: {} (可选: true, false)", name, value)),
+        Err(_) => Ok(false),
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Some(true),
+        "false" | "0" | "no" | "off" | "" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region_urls() {
+        let config = Config::new("key", "secret", "pass", Region::Eea, Environment::Live);
+        assert_eq!(config.base_url, "https://eea.okx.com");
+        assert_eq!(config.ws_public_url, "wss://wseea.okx.com:8443/ws/v5/public");
+        assert!(!config.is_sandbox);
+
+        let config = Config::new("key", "secret", "pass", Region::Global, Environment::Demo);
+        assert_eq!(config.base_url, "https://www.okx.com");
+        assert_eq!(config.ws_private_url, "wss://wspap.okx.com:8443/ws/v5/private");
+        assert!(config.is_sandbox);
+        assert_eq!(config.environment(), Environment::Demo);
+    }
+
+    #[test]
+    fn test_region_from_str() {
+        assert_eq!("US".parse::<Region>().unwrap(), Region::Us);
+        assert_eq!("app".parse::<Region>().unwrap(), Region::App);
+        assert!("mars".parse::<Region>().is_err());
+    }
+
+    #[test]
+    fn test_parse_bool() {
+        assert_eq!(parse_bool("TRUE"), Some(true));
+        assert_eq!(parse_bool("1"), Some(true));
+        assert_eq!(parse_bool("false"), Some(false));
+        assert_eq!(parse_bool("demo"), None);
+    }
+}
@@ -0,0 +1,335 @@
+use serde_json::Value;
+use thiserror::Error;
+
+/// OKX错误码：请求过于频繁
+pub const CODE_RATE_LIMITED: &str = "50011";
+/// OKX错误码：API Key与当前环境不匹配（实盘/模拟盘）
+pub const CODE_ENV_MISMATCH: &str = "50101";
+/// OKX错误码：时间戳过期
+pub const CODE_TIMESTAMP_EXPIRED: &str = "50102";
+/// OKX错误码：签名无效
+pub const CODE_INVALID_SIGN: &str = "50113";
+
+/// 批量接口中单个条目的错误（来自 `data[].sCode/sMsg`）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemError {
+    /// 条目在 `data` 数组中的位置
+    pub index: usize,
+    /// 条目错误码
+    pub code: String,
+    /// 条目错误信息
+    pub msg: String,
+}
+
+/// OKX API 错误
+#[derive(Debug, Error)]
+pub enum OkxError {
+    /// HTTP状态码非2xx，响应体中如有 code/msg 会一并解析
+    #[error("HTTP错误: {status} - {msg} ({path})")]
+    Http {
+        path: String,
+        status: u16,
+        code: Option<String>,
+        msg: String,
+    },
+    /// HTTP成功但OKX返回非0业务码
+    #[error("API错误: {code} - {msg} ({path})")]
+    Api {
+        path: String,
+        status: u16,
+        code: String,
+        msg: String,
+        items: Vec<ItemError>,
+    },
+    /// 网络错误（连接失败、超时等）
+    #[error("网络错误 ({path}): {source}")]
+    Network {
+        path: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    /// 响应无法解析
+    #[error("响应解析失败 ({path}): {source}")]
+    Decode {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    /// 请求构建失败（序列化、签名等）
+    #[error("请求构建失败: {0}")]
+    Request(String),
+}
+
+impl OkxError {
+    /// 根据非2xx响应构建HTTP错误
+    pub(crate) fn http(path: &str, status: u16, body: &str) -> Self {
+        let value: Option<Value> = serde_json::from_str(body).ok();
+        let code = value
+            .as_ref()
+            .and_then(|v| v["code"].as_str())
+            .map(|s| s.to_string());
+        let msg = value
+            .as_ref()
+            .and_then(|v| v["msg"].as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| body.to_string());
+
+        OkxError::Http {
+            path: path.to_string(),
+            status,
+            code,
+            msg,
+        }
+    }
+
+    /// 检查响应JSON中的业务码，非0时返回API错误
+    pub(crate) fn check_api(path: &str, status: u16, value: &Value) -> Result<(), OkxError> {
+        let code = match value["code"].as_str() {
+            Some(code) if code != "0" => code,
+            _ => return Ok(()),
+        };
+
+        Err(OkxError::Api {
+            path: path.to_string(),
+            status,
+            code: code.to_string(),
+            msg: value["msg"].as_str().unwrap_or("未知错误").to_string(),
+            items: item_errors(value),
+        })
+    }
+
+    /// 响应是否为带逐条结果的全部/部分失败（业务码1或2，且 `data[]` 每条都有 `sCode`）
+    pub(crate) fn has_item_results(value: &Value) -> bool {
+        matches!(value["code"].as_str(), Some("1") | Some("2"))
+            && value["data"].as_array().is_some_and(|items| {
+                !items.is_empty() && items.iter().all(|item| item["sCode"].is_string())
+            })
+    }
+
+    /// 请求路径
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            OkxError::Http { path, .. }
+            | OkxError::Api { path, .. }
+            | OkxError::Network { path, .. }
+            | OkxError::Decode { path, .. } => Some(path),
+            OkxError::Request(_) => None,
+        }
+    }
+
+    /// HTTP状态码
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            OkxError::Http { status, .. } | OkxError::Api { status, .. } => Some(*status),
+            OkxError::Network { source, .. } => source.status().map(|s| s.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// OKX业务错误码
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            OkxError::Http { code, .. } => code.as_deref(),
+            OkxError::Api { code, .. } => Some(code),
+            _ => None,
+        }
+    }
+
+    /// 批量接口中失败的条目
+    pub fn items(&self) -> &[ItemError] {
+        match self {
+            OkxError::Api { items, .. } => items,
+            _ => &[],
+        }
+    }
+
+    /// 是否触发限速（HTTP 429 或 50011）
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(429) || self.code() == Some(CODE_RATE_LIMITED)
+    }
+
+    /// 时间戳是否过期（50102），通常意味着本地时钟偏差
+    pub fn is_timestamp_expired(&self) -> bool {
+        self.code() == Some(CODE_TIMESTAMP_EXPIRED)
+    }
+
+    /// API Key与当前环境是否不匹配（50101）
+    ///
+    /// 通常是模拟盘密钥未设置 `OKX_SANDBOX=true`，或实盘密钥开启了模拟盘。
+    pub fn is_environment_mismatch(&self) -> bool {
+        self.code() == Some(CODE_ENV_MISMATCH)
+    }
+
+    /// 是否为认证错误（HTTP 401 或 501xx 系列）
+    pub fn is_auth_error(&self) -> bool {
+        if self.status() == Some(401) {
+            return true;
+        }
+        matches!(self.code(), Some(code) if code.starts_with("501") && code.len() == 5)
+    }
+
+    /// 是否余额不足（包括批量接口中的条目错误）
+    pub fn is_insufficient_balance(&self) -> bool {
+        const CODES: [&str; 3] = ["51008", "51127", "58350"];
+        self.code().is_some_and(|code| CODES.contains(&code))
+            || self.items().iter().any(|item| CODES.contains(&item.code.as_str()))
+    }
+
+    /// 是否为临时性错误，可以重试
+    ///
+    /// 包括限速、5xx、网络超时/连接失败，以及OKX的系统繁忙类错误码。
+    pub fn is_retryable(&self) -> bool {
+        if self.is_rate_limited() {
+            return true;
+        }
+        match self {
+            OkxError::Http { status, .. } => *status >= 500,
+            OkxError::Api { code, .. } => {
+                matches!(code.as_str(), "50001" | "50004" | "50013" | "50026")
+            }
+            OkxError::Network { source, .. } => source.is_timeout() || source.is_connect(),
+            _ => false,
+        }
+    }
+}
+
+/// 提取 `data[]` 中 sCode 非0的条目
+fn item_errors(value: &Value) -> Vec<ItemError> {
+    value["data"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .enumerate()
+                .filter_map(|(index, item)| {
+                    let code = item["sCode"].as_str()?;
+                    if code == "0" {
+                        return None;
+                    }
+                    Some(ItemError {
+                        index,
+                        code: code.to_string(),
+                        msg: item["sMsg"].as_str().unwrap_or("").to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_api_success() {
+        let value: Value = serde_json::from_str(r#"{"code":"0","msg":"","data":[]}"#).unwrap();
+        assert!(OkxError::check_api("/api/v5/account/balance", 200, &value).is_ok());
+    }
+
+    #[test]
+    fn test_check_api_rate_limited() {
+        let value: Value =
+            serde_json::from_str(r#"{"code":"50011","msg":"Too Many Requests","data":[]}"#).unwrap();
+        let err = OkxError::check_api("/api/v5/account/positions", 200, &value).unwrap_err();
+
+        assert_eq!(err.code(), Some("50011"));
+        assert_eq!(err.path(), Some("/api/v5/account/positions"));
+        assert!(err.is_rate_limited());
+        assert!(err.is_retryable());
+        assert!(!err.is_auth_error());
+    }
+
+    #[test]
+    fn test_has_item_results() {
+        let partial: Value = serde_json::from_str(
+            r#"{"code":"2","msg":"","data":[{"ordId":"1","sCode":"0","sMsg":""},{"ordId":"","sCode":"51008","sMsg":"Insufficient balance"}]}"#,
+        )
+        .unwrap();
+        assert!(OkxError::has_item_results(&partial));
+
+        // 业务码1但没有逐条结果，仍按普通错误处理
+        let failed: Value =
+            serde_json::from_str(r#"{"code":"1","msg":"Operation failed","data":[]}"#).unwrap();
+        assert!(!OkxError::has_item_results(&failed));
+
+        let rate_limited: Value =
+            serde_json::from_str(r#"{"code":"50011","msg":"Too Many Requests","data":[]}"#)
+                .unwrap();
+        assert!(!OkxError::has_item_results(&rate_limited));
+    }
+
+    #[test]
+    fn test_http_error_parses_body() {
+        let err = OkxError::http(
+            "/api/v5/account/balance",
+            401,
+            r#"{"msg":"Invalid Sign","code":"50113"}"#,
+        );
+
+        assert_eq!(err.status(), Some(401));
+        assert_eq!(err.code(), Some(CODE_INVALID_SIGN));
+        assert!(err.is_auth_error());
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn test_http_error_plain_body() {
+        let err = OkxError::http("/api/v5/account/balance", 503, "Service Unavailable");
+
+        assert_eq!(err.code(), None);
+        assert!(err.is_retryable());
+        assert!(err.to_string().contains("Service Unavailable"));
+    }
+
+    #[test]
+    fn test_timestamp_expired() {
+        let value: Value = serde_json::from_str(
+            r#"{"code":"50102","msg":"Timestamp request expired","data":[]}"#,
+        )
+        .unwrap();
+        let err = OkxError::check_api("/api/v5/account/balance", 200, &value).unwrap_err();
+
+        assert!(err.is_timestamp_expired());
+        assert!(err.is_auth_error());
+    }
+
+    #[test]
+    fn test_environment_mismatch() {
+        let err = OkxError::http(
+            "/api/v5/account/balance",
+            401,
+            r#"{"msg":"APIKey does not match current environment.","code":"50101"}"#,
+        );
+
+        assert!(err.is_environment_mismatch());
+        assert!(err.is_auth_error());
+    }
+
+    #[test]
+    fn test_item_errors() {
+        let value: Value = serde_json::from_str(
+            r#"{
+                "code": "1",
+                "msg": "All operations failed",
+                "data": [
+                    {"ordId": "", "clOrdId": "a", "sCode": "51008", "sMsg": "Order failed. Insufficient balance"},
+                    {"ordId": "1", "clOrdId": "b", "sCode": "0", "sMsg": ""}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let err = OkxError::check_api("/api/v5/trade/batch-orders", 200, &value).unwrap_err();
+
+        assert_eq!(
+            err.items(),
+            &[ItemError {
+                index: 0,
+                code: "51008".to_string(),
+                msg: "Order failed. Insufficient balance".to_string(),
+            }]
+        );
+        assert!(err.is_insufficient_balance());
+        assert!(!err.is_retryable());
+    }
+}
@@ -0,0 +1,316 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::{
+    client::OkxClient,
+    error::OkxError,
+    time::opt_timestamp_ms,
+    types::{decimal, format_decimal, ApiResponse, ExecType, InstType},
+};
+
+/// 手续费费率
+///
+/// OKX 费率符号约定：负数为手续费（扣除），正数为返佣，与账单和历史持仓中 `fee` 的符号一致。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeFee {
+    /// 产品类型
+    #[serde(rename = "instType")]
+    pub inst_type: InstType,
+    /// 手续费等级
+    #[serde(default)]
+    pub level: String,
+    /// 挂单费率：币币为非USDT/USDC交易对，合约为币本位
+    #[serde(default, with = "decimal")]
+    pub maker: Option<Decimal>,
+    /// 吃单费率：币币为非USDT/USDC交易对，合约为币本位
+    #[serde(default, with = "decimal")]
+    pub taker: Option<Decimal>,
+    /// USDT挂单费率：币币为USDT交易对，合约为USDT本位
+    #[serde(rename = "makerU", default, with = "decimal")]
+    pub maker_u: Option<Decimal>,
+    /// USDT吃单费率
+    #[serde(rename = "takerU", default, with = "decimal")]
+    pub taker_u: Option<Decimal>,
+    /// USDC挂单费率
+    #[serde(rename = "makerUSDC", default, with = "decimal")]
+    pub maker_usdc: Option<Decimal>,
+    /// USDC吃单费率
+    #[serde(rename = "takerUSDC", default, with = "decimal")]
+    pub taker_usdc: Option<Decimal>,
+    /// 交割手续费率
+    #[serde(default, with = "decimal")]
+    pub delivery: Option<Decimal>,
+    /// 行权手续费率
+    #[serde(default, with = "decimal")]
+    pub exercise: Option<Decimal>,
+    /// 数据返回时间
+    #[serde(default, with = "opt_timestamp_ms")]
+    pub ts: Option<DateTime<Utc>>,
+}
+
+/// 某个产品适用的挂单、吃单费率
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeRates {
+    pub maker: Decimal,
+    pub taker: Decimal,
+}
+
+impl FeeRates {
+    /// 按流动性方向取费率
+    pub fn rate(&self, exec_type: &ExecType) -> Decimal {
+        match exec_type {
+            ExecType::Maker => self.maker,
+            _ => self.taker,
+        }
+    }
+
+    /// 估算开仓加平仓的手续费，`notional` 为名义价值（计价或结算币种）
+    pub fn estimate_round_trip(
+        &self,
+        notional: Decimal,
+        open: &ExecType,
+        close: &ExecType,
+    ) -> RoundTripFee {
+        let open_fee = notional * self.rate(open);
+        let close_fee = notional * self.rate(close);
+        RoundTripFee {
+            open_fee,
+            close_fee,
+            total: open_fee + close_fee,
+        }
+    }
+}
+
+/// 往返手续费估算，负数为支出
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RoundTripFee {
+    pub open_fee: Decimal,
+    pub close_fee: Decimal,
+    pub total: Decimal,
+}
+
+impl TradeFee {
+    /// 按产品ID选择适用的费率：USDT、USDC 交易对或保证金合约使用对应的费率，其余使用币本位费率
+    pub fn rates_for(&self, inst_id: &str) -> Option<FeeRates> {
+        let parts: Vec<&str> = inst_id.split('-').collect();
+        let quote = parts.get(1).copied().unwrap_or("");
+
+        let (maker, taker) = match quote {
+            "USDT" if self.maker_u.is_some() => (self.maker_u, self.taker_u),
+            "USDC" if self.maker_usdc.is_some() => (self.maker_usdc, self.taker_usdc),
+            _ => (self.maker, self.taker),
+        };
+        Some(FeeRates {
+            maker: maker?,
+            taker: taker?,
+        })
+    }
+
+    /// 一行摘要
+    pub fn format_summary(&self) -> String {
+        format!(
+            "{} {} | 币本位 maker {} taker {} | USDT maker {} taker {} | USDC maker {} taker {}",
+            self.inst_type,
+            self.level,
+            format_decimal(&self.maker),
+            format_decimal(&self.taker),
+            format_decimal(&self.maker_u),
+            format_decimal(&self.taker_u),
+            format_decimal(&self.maker_usdc),
+            format_decimal(&self.taker_usdc)
+        )
+    }
+}
+
+/// 手续费费率查询参数
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct TradeFeeParams {
+    pub inst_type: InstType,
+    /// 产品ID，仅适用于币币和币币杠杆
+    pub inst_id: Option<String>,
+    /// 交易品种，适用于交割、永续和期权，如 `BTC-USD`
+    pub inst_family: Option<String>,
+}
+
+impl TradeFeeParams {
+    pub fn to_query_params(&self) -> HashMap<String, String> {
+        let mut params = HashMap::new();
+
+        params.insert("instType".to_string(), self.inst_type.to_string());
+        if let Some(ref inst_id) = self.inst_id {
+            params.insert("instId".to_string(), inst_id.clone());
+        }
+        if let Some(ref inst_family) = self.inst_family {
+            params.insert("instFamily".to_string(), inst_family.clone());
+        }
+
+        params
+    }
+}
+
+impl OkxClient {
+    /// 获取当前账户的手续费费率
+    pub async fn get_trade_fee(
+        &self,
+        params: &TradeFeeParams,
+    ) -> Result<ApiResponse<TradeFee>, OkxError> {
+        let query_params = params.to_query_params();
+        self.get("/api/v5/account/trade-fee", Some(&query_params))
+            .await
+    }
+}
+
+/// 费率缓存默认有效期：1小时
+const DEFAULT_FEE_TTL: Duration = Duration::from_secs(3600);
+
+/// 手续费费率缓存
+///
+/// 按查询参数缓存 `trade-fee` 的结果，过期后重新请求；可在多个任务间共享。
+pub struct TradeFeeCache {
+    client: OkxClient,
+    ttl: Duration,
+    entries: Mutex<HashMap<TradeFeeParams, (Instant, TradeFee)>>,
+}
+
+impl TradeFeeCache {
+    pub fn new(client: OkxClient) -> Self {
+        Self::with_ttl(client, DEFAULT_FEE_TTL)
+    }
+
+    pub fn with_ttl(client: OkxClient, ttl: Duration) -> Self {
+        Self {
+            client,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 获取费率，缓存有效时不发请求
+    pub async fn get(&self, params: &TradeFeeParams) -> Result<TradeFee, OkxError> {
+        if let Some(fee) = self.cached(params, Instant::now()) {
+            return Ok(fee);
+        }
+
+        let fee = self
+            .client
+            .get_trade_fee(params)
+            .await?
+            .data
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                OkxError::Request(format!("未返回 {} 的手续费费率", params.inst_type))
+            })?;
+        self.insert(params.clone(), fee.clone(), Instant::now());
+        Ok(fee)
+    }
+
+    /// 获取指定产品适用的挂单、吃单费率
+    pub async fn rates_for(
+        &self,
+        params: &TradeFeeParams,
+        inst_id: &str,
+    ) -> Result<FeeRates, OkxError> {
+        self.get(params)
+            .await?
+            .rates_for(inst_id)
+            .ok_or_else(|| OkxError::Request(format!("{} 没有可用的手续费费率", inst_id)))
+    }
+
+    /// 清空缓存
+    pub fn invalidate(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    fn cached(&self, params: &TradeFeeParams, now: Instant) -> Option<TradeFee> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(params)
+            .filter(|(fetched, _)| now.saturating_duration_since(*fetched) < self.ttl)
+            .map(|(_, fee)| fee.clone())
+    }
+
+    fn insert(&self, params: TradeFeeParams, fee: TradeFee, now: Instant) {
+        self.entries.lock().unwrap().insert(params, (now, fee));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn swap_fee() -> TradeFee {
+        serde_json::from_str(
+            r#"{
+                "category": "1", "delivery": "", "exercise": "", "instType": "SWAP",
+                "level": "Lv1", "maker": "-0.0002", "makerU": "-0.0002", "makerUSDC": "-0.00016",
+                "taker": "-0.0005", "takerU": "-0.0005", "takerUSDC": "-0.0004",
+                "ruleType": "normal", "ts": "1763979985847", "fiat": []
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn params() -> TradeFeeParams {
+        TradeFeeParams {
+            inst_type: InstType::Swap,
+            inst_id: None,
+            inst_family: None,
+        }
+    }
+
+    #[test]
+    fn test_rates_for_instrument() {
+        let fee = swap_fee();
+
+        let usdc = fee.rates_for("BTC-USDC-SWAP").unwrap();
+        assert_eq!(usdc.maker, "-0.00016".parse().unwrap());
+        assert_eq!(usdc.taker, "-0.0004".parse().unwrap());
+
+        let coin = fee.rates_for("BTC-USD-SWAP").unwrap();
+        assert_eq!(coin.taker, "-0.0005".parse().unwrap());
+    }
+
+    #[test]
+    fn test_estimate_round_trip() {
+        let rates = swap_fee().rates_for("BTC-USDT-SWAP").unwrap();
+        let notional = Decimal::from(10_000);
+
+        let fee = rates.estimate_round_trip(notional, &ExecType::Maker, &ExecType::Taker);
+        assert_eq!(fee.open_fee, Decimal::from(-2));
+        assert_eq!(fee.close_fee, Decimal::from(-5));
+        assert_eq!(fee.total, Decimal::from(-7));
+    }
+
+    #[test]
+    fn test_cache_expiry() {
+        let cache = TradeFeeCache::with_ttl(
+            OkxClient::new(Config::test_default()),
+            Duration::from_secs(60),
+        );
+        let now = Instant::now();
+
+        assert!(cache.cached(&params(), now).is_none());
+        cache.insert(params(), swap_fee(), now);
+        assert!(cache
+            .cached(&params(), now + Duration::from_secs(59))
+            .is_some());
+        assert!(cache
+            .cached(&params(), now + Duration::from_secs(60))
+            .is_none());
+
+        let spot = TradeFeeParams {
+            inst_type: InstType::Spot,
+            ..params()
+        };
+        assert!(cache.cached(&spot, now).is_none());
+
+        cache.invalidate();
+        assert!(cache.cached(&params(), now).is_none());
+    }
+}
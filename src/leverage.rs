@@ -0,0 +1,209 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{
+    client::OkxClient,
+    error::OkxError,
+    types::{decimal, format_decimal, opt_enum, ApiResponse, MarginMode, PosSide},
+};
+
+/// 杠杆倍数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeverageInfo {
+    /// 产品ID，按币种设置币币杠杆全仓杠杆时为空
+    #[serde(rename = "instId", default)]
+    pub inst_id: String,
+    /// 币种，仅币币杠杆全仓按币种设置时有值
+    #[serde(default)]
+    pub ccy: String,
+    /// 保证金模式
+    #[serde(rename = "mgnMode")]
+    pub mgn_mode: MarginMode,
+    /// 持仓方向，仅开平仓模式下的逐仓有值
+    #[serde(rename = "posSide", default, with = "opt_enum")]
+    pub pos_side: Option<PosSide>,
+    /// 杠杆倍数
+    #[serde(default, with = "decimal")]
+    pub lever: Option<Decimal>,
+}
+
+impl LeverageInfo {
+    /// 表格表头，与 [`LeverageInfo::format_display`] 的列对齐
+    pub fn table_header() -> String {
+        format!(
+            "{:<20} | {:<6} | {:<8} | {:<7} | {:<6}",
+            "InstId", "Ccy", "MgnMode", "PosSide", "Lever"
+        )
+    }
+
+    /// 表格格式输出
+    pub fn format_display(&self) -> String {
+        format!(
+            "{:<20} | {:<6} | {:<8} | {:<7} | {:<6}",
+            self.inst_id,
+            self.ccy,
+            self.mgn_mode,
+            self.pos_side.as_ref().map(|s| s.as_str()).unwrap_or(""),
+            format_decimal(&self.lever)
+        )
+    }
+}
+
+/// 杠杆倍数查询参数，`inst_id` 与 `ccy` 至少指定一个，均可用逗号分隔多个
+#[derive(Debug, Clone, Serialize)]
+pub struct LeverageInfoParams {
+    pub inst_id: Option<String>,
+    pub ccy: Option<String>,
+    pub mgn_mode: MarginMode,
+}
+
+impl LeverageInfoParams {
+    pub fn to_query_params(&self) -> HashMap<String, String> {
+        let mut params = HashMap::new();
+
+        if let Some(ref inst_id) = self.inst_id {
+            params.insert("instId".to_string(), inst_id.clone());
+        }
+        if let Some(ref ccy) = self.ccy {
+            params.insert("ccy".to_string(), ccy.clone());
+        }
+        params.insert("mgnMode".to_string(), self.mgn_mode.to_string());
+
+        params
+    }
+}
+
+/// 设置杠杆倍数请求
+///
+/// 按产品设置时指定 `inst_id`；币币杠杆全仓按币种设置时指定 `ccy`。
+/// 开平仓模式下的逐仓需要指定 `pos_side`。
+#[derive(Debug, Clone, Serialize)]
+pub struct SetLeverageRequest {
+    #[serde(rename = "instId", skip_serializing_if = "Option::is_none")]
+    pub inst_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ccy: Option<String>,
+    pub lever: Decimal,
+    #[serde(rename = "mgnMode")]
+    pub mgn_mode: MarginMode,
+    #[serde(rename = "posSide", skip_serializing_if = "Option::is_none")]
+    pub pos_side: Option<PosSide>,
+}
+
+impl SetLeverageRequest {
+    /// 对应的杠杆倍数查询参数
+    pub fn info_params(&self) -> LeverageInfoParams {
+        LeverageInfoParams {
+            inst_id: self.inst_id.clone(),
+            ccy: self.ccy.clone(),
+            mgn_mode: self.mgn_mode.clone(),
+        }
+    }
+
+    fn validate(&self) -> Result<(), OkxError> {
+        if self.inst_id.is_none() && self.ccy.is_none() {
+            return Err(OkxError::Request(
+                "设置杠杆倍数需要指定 instId 或 ccy".to_string(),
+            ));
+        }
+        if self.lever <= Decimal::ZERO {
+            return Err(OkxError::Request(format!("无效的杠杆倍数: {}", self.lever)));
+        }
+        Ok(())
+    }
+}
+
+impl OkxClient {
+    /// 获取杠杆倍数
+    pub async fn get_leverage_info(
+        &self,
+        params: &LeverageInfoParams,
+    ) -> Result<ApiResponse<LeverageInfo>, OkxError> {
+        if params.inst_id.is_none() && params.ccy.is_none() {
+            return Err(OkxError::Request(
+                "查询杠杆倍数需要指定 instId 或 ccy".to_string(),
+            ));
+        }
+
+        let query_params = params.to_query_params();
+        self.get("/api/v5/account/leverage-info", Some(&query_params))
+            .await
+    }
+
+    /// 设置杠杆倍数
+    pub async fn set_leverage(
+        &self,
+        request: &SetLeverageRequest,
+    ) -> Result<ApiResponse<LeverageInfo>, OkxError> {
+        request.validate()?;
+        self.post("/api/v5/account/set-leverage", Some(request))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_leverage_request_serialization() {
+        let request = SetLeverageRequest {
+            inst_id: Some("BTC-USDT-SWAP".to_string()),
+            ccy: None,
+            lever: Decimal::from(5),
+            mgn_mode: MarginMode::Isolated,
+            pos_side: Some(PosSide::Long),
+        };
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "instId": "BTC-USDT-SWAP",
+                "lever": "5",
+                "mgnMode": "isolated",
+                "posSide": "long"
+            })
+        );
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_set_leverage_request_validation() {
+        let mut request = SetLeverageRequest {
+            inst_id: None,
+            ccy: None,
+            lever: Decimal::from(3),
+            mgn_mode: MarginMode::Cross,
+            pos_side: None,
+        };
+        assert!(request.validate().is_err());
+
+        request.ccy = Some("BTC".to_string());
+        assert!(request.validate().is_ok());
+
+        request.lever = Decimal::ZERO;
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_leverage_info_deserialization() {
+        let json = r#"{
+            "code": "0",
+            "msg": "",
+            "data": [
+                {"ccy": "", "instId": "BTC-USDT-SWAP", "mgnMode": "isolated", "posSide": "long", "lever": "10"},
+                {"ccy": "", "instId": "BTC-USDT-SWAP", "mgnMode": "isolated", "posSide": "short", "lever": "5"},
+                {"ccy": "BTC", "instId": "", "mgnMode": "cross", "posSide": "", "lever": "3"}
+            ]
+        }"#;
+
+        let response: ApiResponse<LeverageInfo> = serde_json::from_str(json).unwrap();
+        assert_eq!(response.data[0].pos_side, Some(PosSide::Long));
+        assert_eq!(response.data[1].lever, Some(Decimal::from(5)));
+        assert_eq!(response.data[2].pos_side, None);
+        assert_eq!(response.data[2].mgn_mode, MarginMode::Cross);
+        assert!(response.data[2].format_display().contains("BTC"));
+    }
+}
@@ -1,7 +1,20 @@
 pub mod account;
+pub mod bills;
+pub mod borrow;
 pub mod client;
+pub mod clock;
 pub mod config;
+pub mod error;
+pub mod fees;
+pub mod leverage;
+pub mod limits;
+pub mod pager;
+pub mod position_builder;
 pub mod positions;
 pub mod positions_history;
 pub mod rate_limiter;
+pub mod retry;
+pub mod risk;
+pub mod time;
+pub mod trade;
 pub mod types; 
\ No newline at end of file
@@ -0,0 +1,245 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{
+    client::OkxClient,
+    error::OkxError,
+    types::{decimal, format_decimal, ApiResponse, TradeMode},
+};
+
+/// 最大可下单数量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaxSize {
+    /// 产品ID
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    /// 保证金币种
+    #[serde(default)]
+    pub ccy: String,
+    /// 最大可买：币币/币币杠杆为交易货币数量，合约为张数
+    #[serde(rename = "maxBuy", default, with = "decimal")]
+    pub max_buy: Option<Decimal>,
+    /// 最大可卖：币币/币币杠杆为计价货币数量，合约为张数
+    #[serde(rename = "maxSell", default, with = "decimal")]
+    pub max_sell: Option<Decimal>,
+}
+
+/// 最大可用数量（不含借币）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaxAvailSize {
+    /// 产品ID
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    /// 最大买入可用数量
+    #[serde(rename = "availBuy", default, with = "decimal")]
+    pub avail_buy: Option<Decimal>,
+    /// 最大卖出可用数量
+    #[serde(rename = "availSell", default, with = "decimal")]
+    pub avail_sell: Option<Decimal>,
+}
+
+/// 最大可转余额
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaxWithdrawal {
+    /// 币种
+    pub ccy: String,
+    /// 最大可划转数量（不包含跨币种保证金模式借币金额）
+    #[serde(rename = "maxWd", default, with = "decimal")]
+    pub max_wd: Option<Decimal>,
+    /// 最大可划转数量（包含跨币种保证金模式借币金额）
+    #[serde(rename = "maxWdEx", default, with = "decimal")]
+    pub max_wd_ex: Option<Decimal>,
+}
+
+/// 最大可下单/可用数量查询参数
+#[derive(Debug, Clone, Serialize)]
+pub struct MaxSizeParams {
+    /// 产品ID，可用逗号分隔多个
+    pub inst_id: String,
+    pub td_mode: TradeMode,
+    /// 保证金币种，仅适用于全仓币币杠杆
+    pub ccy: Option<String>,
+    /// 委托价格，不填时按最新成交价计算（仅用于 max-size）
+    pub px: Option<Decimal>,
+}
+
+impl MaxSizeParams {
+    pub fn to_query_params(&self) -> HashMap<String, String> {
+        let mut params = HashMap::new();
+
+        params.insert("instId".to_string(), self.inst_id.clone());
+        params.insert("tdMode".to_string(), self.td_mode.to_string());
+        if let Some(ref ccy) = self.ccy {
+            params.insert("ccy".to_string(), ccy.clone());
+        }
+        if let Some(px) = self.px {
+            params.insert("px".to_string(), px.to_string());
+        }
+
+        params
+    }
+}
+
+/// 某个产品的下单限额汇总
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderLimits {
+    pub inst_id: String,
+    pub max_size: Option<MaxSize>,
+    pub avail_size: Option<MaxAvailSize>,
+}
+
+impl OrderLimits {
+    /// 按产品合并两个接口的结果
+    pub fn merge(max_sizes: Vec<MaxSize>, avail_sizes: Vec<MaxAvailSize>) -> Vec<OrderLimits> {
+        let mut limits: Vec<OrderLimits> = max_sizes
+            .into_iter()
+            .map(|max_size| OrderLimits {
+                inst_id: max_size.inst_id.clone(),
+                max_size: Some(max_size),
+                avail_size: None,
+            })
+            .collect();
+
+        for avail_size in avail_sizes {
+            match limits.iter_mut().find(|l| l.inst_id == avail_size.inst_id) {
+                Some(limit) => limit.avail_size = Some(avail_size),
+                None => limits.push(OrderLimits {
+                    inst_id: avail_size.inst_id.clone(),
+                    max_size: None,
+                    avail_size: Some(avail_size),
+                }),
+            }
+        }
+        limits
+    }
+
+    /// 表格表头，与 [`OrderLimits::format_display`] 的列对齐
+    pub fn table_header() -> String {
+        format!(
+            "{:<20} | {:<6} | {:<16} | {:<16} | {:<16} | {:<16}",
+            "InstId", "Ccy", "MaxBuy", "MaxSell", "AvailBuy", "AvailSell"
+        )
+    }
+
+    /// 表格格式输出
+    pub fn format_display(&self) -> String {
+        let max_size = self.max_size.as_ref();
+        let avail_size = self.avail_size.as_ref();
+        format!(
+            "{:<20} | {:<6} | {:<16} | {:<16} | {:<16} | {:<16}",
+            self.inst_id,
+            max_size.map(|m| m.ccy.as_str()).unwrap_or(""),
+            format_decimal(&max_size.and_then(|m| m.max_buy)),
+            format_decimal(&max_size.and_then(|m| m.max_sell)),
+            format_decimal(&avail_size.and_then(|a| a.avail_buy)),
+            format_decimal(&avail_size.and_then(|a| a.avail_sell))
+        )
+    }
+}
+
+impl OkxClient {
+    /// 获取最大可下单数量
+    pub async fn get_max_size(
+        &self,
+        params: &MaxSizeParams,
+    ) -> Result<ApiResponse<MaxSize>, OkxError> {
+        let query_params = params.to_query_params();
+        self.get("/api/v5/account/max-size", Some(&query_params))
+            .await
+    }
+
+    /// 获取最大可用数量
+    pub async fn get_max_avail_size(
+        &self,
+        params: &MaxSizeParams,
+    ) -> Result<ApiResponse<MaxAvailSize>, OkxError> {
+        let mut query_params = params.to_query_params();
+        // 该接口的 px 是币币杠杆只减仓的平仓价格，含义与 max-size 不同
+        query_params.remove("px");
+        self.get("/api/v5/account/max-avail-size", Some(&query_params))
+            .await
+    }
+
+    /// 获取最大可转余额，`ccy` 为空时返回所有币种
+    pub async fn get_max_withdrawal(
+        &self,
+        ccy: Option<&str>,
+    ) -> Result<ApiResponse<MaxWithdrawal>, OkxError> {
+        let mut query_params = HashMap::new();
+        if let Some(ccy) = ccy {
+            query_params.insert("ccy".to_string(), ccy.to_string());
+        }
+        self.get("/api/v5/account/max-withdrawal", Some(&query_params))
+            .await
+    }
+
+    /// 同时查询最大可下单数量和最大可用数量，按产品合并
+    pub async fn get_order_limits(
+        &self,
+        params: &MaxSizeParams,
+    ) -> Result<Vec<OrderLimits>, OkxError> {
+        let (max_size, avail_size) =
+            tokio::join!(self.get_max_size(params), self.get_max_avail_size(params));
+        Ok(OrderLimits::merge(max_size?.data, avail_size?.data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_size_params() {
+        let params = MaxSizeParams {
+            inst_id: "BTC-USDT".to_string(),
+            td_mode: TradeMode::Cross,
+            ccy: Some("USDT".to_string()),
+            px: Some("42000.5".parse().unwrap()),
+        };
+
+        let query_params = params.to_query_params();
+        assert_eq!(query_params.get("instId"), Some(&"BTC-USDT".to_string()));
+        assert_eq!(query_params.get("tdMode"), Some(&"cross".to_string()));
+        assert_eq!(query_params.get("px"), Some(&"42000.5".to_string()));
+    }
+
+    #[test]
+    fn test_merge_limits() {
+        let max_sizes: ApiResponse<MaxSize> = serde_json::from_str(
+            r#"{"code": "0", "msg": "", "data": [
+                {"ccy": "BTC", "instId": "BTC-USDT", "maxBuy": "0.0500695098559788", "maxSell": "64.4798671570072269"},
+                {"ccy": "USDT", "instId": "ETH-USDT-SWAP", "maxBuy": "120", "maxSell": "120"}
+            ]}"#,
+        )
+        .unwrap();
+        let avail_sizes: ApiResponse<MaxAvailSize> = serde_json::from_str(
+            r#"{"code": "0", "msg": "", "data": [
+                {"instId": "BTC-USDT", "availBuy": "100", "availSell": "0.01"},
+                {"instId": "LTC-USDT", "availBuy": "", "availSell": "3"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let limits = OrderLimits::merge(max_sizes.data, avail_sizes.data);
+        assert_eq!(limits.len(), 3);
+        assert_eq!(
+            limits[0].avail_size.as_ref().unwrap().avail_buy,
+            Some(Decimal::from(100))
+        );
+        assert!(limits[1].avail_size.is_none());
+        assert!(limits[2].max_size.is_none());
+
+        let row = limits[0].format_display();
+        assert!(row.contains("0.0500695098559788"));
+        assert!(row.contains("0.01"));
+    }
+
+    #[test]
+    fn test_max_withdrawal_deserialization() {
+        let json = r#"{"ccy": "BTC", "maxWd": "124", "maxWdEx": "125", "spotOffsetMaxWd": "", "spotOffsetMaxWdEx": ""}"#;
+        let withdrawal: MaxWithdrawal = serde_json::from_str(json).unwrap();
+        assert_eq!(withdrawal.max_wd, Some(Decimal::from(124)));
+        assert_eq!(withdrawal.max_wd_ex, Some(Decimal::from(125)));
+    }
+}
@@ -1,11 +1,54 @@
 use clap::{App, Arg, SubCommand};
 use okx_api_client::{
-    account::{get_account_balance, get_account_balance_by_currency, get_account_config},
+    account::BalanceDetail,
+    bills::{Bill, BillsParams, BillsSource},
+    borrow::{daily_interest, BorrowSide, InterestAccruedParams, ManualBorrowRepay},
+    client::OkxClient,
     config::Config,
-    positions::{get_positions, get_positions_simplified},
-    positions_history::{get_positions_history, PositionsHistoryParams},
-    types::PositionsParams,
+    error::OkxError,
+    fees::{TradeFeeCache, TradeFeeParams},
+    leverage::{LeverageInfo, LeverageInfoParams, SetLeverageRequest},
+    limits::{MaxSizeParams, OrderLimits},
+    position_builder::{PositionBuilderRequest, RiskUnit},
+    positions::{MarginAdjustment, MarginBalanceRequest},
+    positions_history::PositionsHistoryParams,
+    risk::Greeks,
+    time::{localize_times, parse_time, DisplayTz},
+    trade::{AmendOrderRequest, AttachAlgoOrder, CancelOrderRequest, PlaceOrderRequest},
+    types::{
+        format_decimal, AccountLevel, ExecType, InstType, OrdType, Position, PositionMode,
+        PositionsParams,
+    },
 };
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+/// 格式化JSON输出，指定时区时把毫秒时间戳转换为可读时间
+fn to_pretty_json(value: &impl Serialize, tz: Option<DisplayTz>) -> anyhow::Result<String> {
+    let mut value = serde_json::to_value(value)?;
+    if let Some(tz) = tz {
+        localize_times(&mut value, tz);
+    }
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
+/// 输出错误并以状态码1退出，密钥与环境不匹配时提示检查 `OKX_SANDBOX`
+fn exit_with_error(context: &str, e: &OkxError) -> ! {
+    eprintln!("{}: {}", context, e);
+    if e.is_environment_mismatch() {
+        eprintln!("提示: API Key与当前环境不匹配，模拟盘密钥需设置 OKX_SANDBOX=true，实盘密钥需设置为 false 或不设置");
+    }
+    std::process::exit(1);
+}
+
+/// 以表格输出杠杆倍数
+fn print_leverage_table(leverages: &[LeverageInfo]) {
+    println!("{}", LeverageInfo::table_header());
+    println!("{:-<59}", "");
+    for leverage in leverages {
+        println!("{}", leverage.format_display());
+    }
+}
 
 fn main() -> anyhow::Result<()> {
     // 创建运行时
@@ -15,10 +58,18 @@ fn main() -> anyhow::Result<()> {
     rt.block_on(async {
         // 加载环境变量
         dotenv::dotenv().ok();
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
 
         let matches = App::new("OKX API Client")
             .version("1.0")
             .about("OKX API 持仓查询工具")
+            .arg(
+                Arg::new("tz")
+                    .long("tz")
+                    .help("输出时间使用的时区 (UTC, local, +08:00, Asia/Shanghai)，不指定时输出毫秒时间戳")
+                    .global(true)
+                    .takes_value(true),
+            )
             .subcommand(
                 SubCommand::with_name("history")
                     .about("查询历史持仓信息")
@@ -47,7 +98,7 @@ fn main() -> anyhow::Result<()> {
                         Arg::new("close_type")
                             .short('c')
                             .long("close-type")
-                            .help("最近一次平仓的类型 (1-5)")
+                            .help("最近一次平仓的类型: 1 部分平仓, 2 完全平仓, 3 强平, 4 强减, 5 ADL自动减仓")
                             .takes_value(true),
                     )
                     .arg(
@@ -61,14 +112,14 @@ fn main() -> anyhow::Result<()> {
                         Arg::new("before")
                             .short('b')
                             .long("before")
-                            .help("查询仓位更新之前的时间戳 (毫秒)")
+                            .help("只返回仓位更新时间晚于该时间的记录，即时间下限 (毫秒时间戳、2024-01-01、ISO 8601 或 7d/12h 等相对时间)")
                             .takes_value(true),
                     )
                     .arg(
                         Arg::new("after")
                             .short('a')
                             .long("after")
-                            .help("查询仓位更新之后的时间戳 (毫秒)")
+                            .help("只返回仓位更新时间早于该时间的记录，即时间上限和翻页起点 (格式同 --before)")
                             .takes_value(true),
                     )
                     .arg(
@@ -78,6 +129,12 @@ fn main() -> anyhow::Result<()> {
                             .help("分页返回结果的数量，最大100")
                             .default_value("100")
                             .takes_value(true),
+                    )
+                    .arg(
+                        Arg::new("all")
+                            .long("all")
+                            .help("自动翻页获取全部历史持仓（受 --before/--after 时间范围限制）")
+                            .takes_value(false),
                     ),
             )
             .subcommand(
@@ -118,6 +175,82 @@ fn main() -> anyhow::Result<()> {
                             .help("输出格式 (json, table)")
                             .default_value("json")
                             .takes_value(true),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("margin")
+                            .about("调整逐仓保证金")
+                            .subcommand(
+                                SubCommand::with_name("add")
+                                    .about("增加逐仓保证金")
+                                    .arg(
+                                        Arg::new("inst_id")
+                                            .short('i')
+                                            .long("inst-id")
+                                            .help("交易产品ID，如：BTC-USDT-SWAP")
+                                            .required(true)
+                                            .takes_value(true),
+                                    )
+                                    .arg(
+                                        Arg::new("pos_side")
+                                            .long("pos-side")
+                                            .help("持仓方向 (long, short, net)")
+                                            .default_value("net")
+                                            .takes_value(true),
+                                    )
+                                    .arg(
+                                        Arg::new("amt")
+                                            .long("amt")
+                                            .help("保证金数量")
+                                            .required(true)
+                                            .takes_value(true),
+                                    )
+                                    .arg(
+                                        Arg::new("ccy")
+                                            .short('c')
+                                            .long("ccy")
+                                            .help("保证金币种，仅适用于币币杠杆逐仓")
+                                            .takes_value(true),
+                                    )
+                                    .arg(
+                                        Arg::new("auto")
+                                            .long("auto")
+                                            .help("自动借币，仅适用于币币杠杆逐仓")
+                                            .takes_value(false),
+                                    ),
+                            )
+                            .subcommand(
+                                SubCommand::with_name("reduce")
+                                    .about("减少逐仓保证金")
+                                    .arg(
+                                        Arg::new("inst_id")
+                                            .short('i')
+                                            .long("inst-id")
+                                            .help("交易产品ID，如：BTC-USDT-SWAP")
+                                            .required(true)
+                                            .takes_value(true),
+                                    )
+                                    .arg(
+                                        Arg::new("pos_side")
+                                            .long("pos-side")
+                                            .help("持仓方向 (long, short, net)")
+                                            .default_value("net")
+                                            .takes_value(true),
+                                    )
+                                    .arg(
+                                        Arg::new("amt")
+                                            .long("amt")
+                                            .help("保证金数量")
+                                            .required(true)
+                                            .takes_value(true),
+                                    )
+                                    .arg(
+                                        Arg::new("ccy")
+                                            .short('c')
+                                            .long("ccy")
+                                            .help("保证金币种，仅适用于币币杠杆逐仓")
+                                            .takes_value(true),
+                                    ),
+                            ),
                     ),
             )
             .subcommand(
@@ -132,17 +265,590 @@ fn main() -> anyhow::Result<()> {
                                     .long("ccy")
                                     .help("币种，如：BTC, ETH")
                                     .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("format")
+                                    .short('f')
+                                    .long("format")
+                                    .help("输出格式 (json, table)")
+                                    .default_value("json")
+                                    .takes_value(true),
                             ),
                     )
                     .subcommand(
                         SubCommand::with_name("config")
-                            .about("查询账户配置"),
+                            .about("查询账户配置")
+                            .arg(
+                                Arg::new("format")
+                                    .short('f')
+                                    .long("format")
+                                    .help("输出格式 (json, table)")
+                                    .default_value("json")
+                                    .takes_value(true),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("bills")
+                            .about("查询账单流水（默认近7天，--archive 查询近3个月）")
+                            .arg(
+                                Arg::new("archive")
+                                    .long("archive")
+                                    .help("查询近3个月的账单归档")
+                                    .takes_value(false),
+                            )
+                            .arg(
+                                Arg::new("inst_type")
+                                    .short('t')
+                                    .long("inst-type")
+                                    .help("产品类型 (SPOT, MARGIN, SWAP, FUTURES, OPTION)")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("inst_id")
+                                    .short('i')
+                                    .long("inst-id")
+                                    .help("交易产品ID，如：BTC-USDT-SWAP")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("ccy")
+                                    .short('c')
+                                    .long("ccy")
+                                    .help("账单币种，如：USDT")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("type")
+                                    .long("type")
+                                    .help("账单类型，如：2 或 trade、8 或 funding-fee、5 或 liquidation、1 或 transfer")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("sub_type")
+                                    .long("sub-type")
+                                    .help("账单子类型，如：173 或 funding-fee-expense")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("begin")
+                                    .long("begin")
+                                    .help("开始时间 (毫秒时间戳、2024-01-01、ISO 8601 或 7d/12h 等相对时间)")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("end")
+                                    .long("end")
+                                    .help("结束时间 (格式同 --begin)")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("limit")
+                                    .short('l')
+                                    .long("limit")
+                                    .help("分页返回结果的数量，最大100")
+                                    .default_value("100")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("all")
+                                    .long("all")
+                                    .help("按 billId 自动翻页获取全部账单")
+                                    .takes_value(false),
+                            )
+                            .arg(
+                                Arg::new("format")
+                                    .short('f')
+                                    .long("format")
+                                    .help("输出格式 (json, table)")
+                                    .default_value("json")
+                                    .takes_value(true),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("leverage")
+                            .about("查询或设置杠杆倍数")
+                            .subcommand(
+                                SubCommand::with_name("get")
+                                    .about("查询杠杆倍数")
+                                    .arg(
+                                        Arg::new("inst_id")
+                                            .short('i')
+                                            .long("inst-id")
+                                            .help("交易产品ID，多个以逗号分隔，如：BTC-USDT-SWAP")
+                                            .takes_value(true),
+                                    )
+                                    .arg(
+                                        Arg::new("ccy")
+                                            .short('c')
+                                            .long("ccy")
+                                            .help("币种，仅适用于币币杠杆全仓")
+                                            .takes_value(true),
+                                    )
+                                    .arg(
+                                        Arg::new("mgn_mode")
+                                            .short('m')
+                                            .long("mgn-mode")
+                                            .help("保证金模式 (cross, isolated)")
+                                            .required(true)
+                                            .takes_value(true),
+                                    ),
+                            )
+                            .subcommand(
+                                SubCommand::with_name("set")
+                                    .about("设置杠杆倍数，并显示调整前后的值")
+                                    .arg(
+                                        Arg::new("inst_id")
+                                            .short('i')
+                                            .long("inst-id")
+                                            .help("交易产品ID，如：BTC-USDT-SWAP")
+                                            .takes_value(true),
+                                    )
+                                    .arg(
+                                        Arg::new("ccy")
+                                            .short('c')
+                                            .long("ccy")
+                                            .help("币种，仅适用于币币杠杆全仓")
+                                            .takes_value(true),
+                                    )
+                                    .arg(
+                                        Arg::new("lever")
+                                            .long("lever")
+                                            .help("杠杆倍数")
+                                            .required(true)
+                                            .takes_value(true),
+                                    )
+                                    .arg(
+                                        Arg::new("mgn_mode")
+                                            .short('m')
+                                            .long("mgn-mode")
+                                            .help("保证金模式 (cross, isolated)")
+                                            .required(true)
+                                            .takes_value(true),
+                                    )
+                                    .arg(
+                                        Arg::new("pos_side")
+                                            .long("pos-side")
+                                            .help("持仓方向 (long, short)，仅开平仓模式下的逐仓需要")
+                                            .takes_value(true),
+                                    ),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("limits")
+                            .about("查询最大可下单数量、最大可用数量，指定 --ccy 时同时查询最大可转余额")
+                            .arg(
+                                Arg::new("inst_id")
+                                    .short('i')
+                                    .long("inst-id")
+                                    .help("交易产品ID，多个以逗号分隔，如：BTC-USDT")
+                                    .required(true)
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("td_mode")
+                                    .short('m')
+                                    .long("td-mode")
+                                    .help("交易模式 (cross, isolated, cash, spot_isolated)")
+                                    .required(true)
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("px")
+                                    .long("px")
+                                    .help("委托价格，不指定时按最新成交价计算")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("ccy")
+                                    .short('c')
+                                    .long("ccy")
+                                    .help("保证金币种（全仓币币杠杆），指定后才会输出该币种的最大可转余额")
+                                    .takes_value(true),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("fees")
+                            .about("查询手续费费率，并可估算往返手续费")
+                            .arg(
+                                Arg::new("inst_type")
+                                    .short('t')
+                                    .long("inst-type")
+                                    .help("产品类型 (SPOT, MARGIN, SWAP, FUTURES, OPTION)")
+                                    .required(true)
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("inst_id")
+                                    .short('i')
+                                    .long("inst-id")
+                                    .help("交易产品ID，用于选择USDT/USDC/币本位费率，如：BTC-USDT-SWAP")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("inst_family")
+                                    .long("inst-family")
+                                    .help("交易品种，适用于交割、永续和期权，如：BTC-USD")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("notional")
+                                    .long("notional")
+                                    .help("估算往返手续费的名义价值（计价或结算币种），需同时指定 --inst-id")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("open")
+                                    .long("open")
+                                    .help("开仓的流动性方向 (maker, taker)")
+                                    .default_value("taker")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("close")
+                                    .long("close")
+                                    .help("平仓的流动性方向 (maker, taker)")
+                                    .default_value("taker")
+                                    .takes_value(true),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("set-position-mode")
+                            .about("切换持仓模式（需先平掉交割、永续持仓并撤销挂单）")
+                            .arg(
+                                Arg::new("mode")
+                                    .help("持仓模式 (long_short_mode 或 long-short, net_mode 或 net)")
+                                    .required(true)
+                                    .index(1),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("set-level")
+                            .about("切换账户模式（需先平仓并撤销挂单）")
+                            .arg(
+                                Arg::new("level")
+                                    .help("账户模式 (1 或 spot, 2 或 futures, 3 或 multi-currency, 4 或 portfolio)")
+                                    .required(true)
+                                    .index(1),
+                            ),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("borrow")
+                    .about("借币利息、利率与手动借还")
+                    .subcommand(
+                        SubCommand::with_name("interest")
+                            .about("按日汇总各币种的利息支出")
+                            .arg(
+                                Arg::new("begin")
+                                    .long("begin")
+                                    .help("开始时间 (毫秒时间戳、2024-01-01、ISO 8601 或 7d/12h 等相对时间)")
+                                    .default_value("7d")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("end")
+                                    .long("end")
+                                    .help("结束时间 (格式同 --begin)，默认为当前时间")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("ccy")
+                                    .short('c')
+                                    .long("ccy")
+                                    .help("借贷币种，如：USDT")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("type")
+                                    .long("type")
+                                    .help("借币类型 (1 尊享借币, 2 市场借币)")
+                                    .takes_value(true),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("rates")
+                            .about("查询当前借币利率与限额")
+                            .arg(
+                                Arg::new("ccy")
+                                    .short('c')
+                                    .long("ccy")
+                                    .help("借贷币种，如：USDT")
+                                    .takes_value(true),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("borrow")
+                            .about("手动借币（现货模式）")
+                            .arg(
+                                Arg::new("ccy")
+                                    .short('c')
+                                    .long("ccy")
+                                    .help("币种，如：USDT")
+                                    .required(true)
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("amt")
+                                    .long("amt")
+                                    .help("数量")
+                                    .required(true)
+                                    .takes_value(true),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("repay")
+                            .about("手动还币（现货模式）")
+                            .arg(
+                                Arg::new("ccy")
+                                    .short('c')
+                                    .long("ccy")
+                                    .help("币种，如：USDT")
+                                    .required(true)
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("amt")
+                                    .long("amt")
+                                    .help("数量")
+                                    .required(true)
+                                    .takes_value(true),
+                            ),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("risk")
+                    .about("查询账户风险：有效保证金、保证金率及各币种希腊字母")
+                    .arg(
+                        Arg::new("ccy")
+                            .short('c')
+                            .long("ccy")
+                            .help("只查询指定币种的希腊字母，如：BTC")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::new("greeks_type")
+                            .short('g')
+                            .long("greeks-type")
+                            .help("希腊字母展示方式 (PA 币本位, BS 美元本位)，默认使用账户配置")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::new("positions")
+                            .short('p')
+                            .long("positions")
+                            .help("同时输出各持仓的名义价值")
+                            .takes_value(false),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("simulate")
+                    .about("模拟假设持仓对保证金的影响（持仓构建器）")
+                    .arg(
+                        Arg::new("file")
+                            .help("JSON或YAML文件（按扩展名 .yaml/.yml 识别），包含 positions (instId, pos, avgPx) 和 assets (ccy, amt)")
+                            .required(true)
+                            .index(1),
+                    )
+                    .arg(
+                        Arg::new("seed")
+                            .long("seed")
+                            .help("把当前持仓加入模拟持仓（不能与 --include-real 同时使用，否则真实持仓会被重复计算）")
+                            .conflicts_with("include_real")
+                            .takes_value(false),
+                    )
+                    .arg(
+                        Arg::new("include_real")
+                            .long("include-real")
+                            .help("由OKX计入账户的真实持仓和资产")
+                            .takes_value(false),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("order")
+                    .about("下单、改单与撤单")
+                    .subcommand(
+                        SubCommand::with_name("place")
+                            .about("下单")
+                            .arg(
+                                Arg::new("inst_id")
+                                    .help("产品ID，如：BTC-USDT-SWAP")
+                                    .required(true)
+                                    .index(1),
+                            )
+                            .arg(
+                                Arg::new("side")
+                                    .help("订单方向 (buy, sell)")
+                                    .required(true)
+                                    .index(2),
+                            )
+                            .arg(
+                                Arg::new("sz")
+                                    .help("委托数量")
+                                    .required(true)
+                                    .index(3),
+                            )
+                            .arg(
+                                Arg::new("px")
+                                    .long("px")
+                                    .help("委托价格，指定时默认为限价单，否则为市价单")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("type")
+                                    .long("type")
+                                    .help("订单类型 (market, limit, post_only, fok, ioc, optimal_limit_ioc)")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("td_mode")
+                                    .long("td-mode")
+                                    .help("交易模式 (cross, isolated, cash)")
+                                    .required(true)
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("pos_side")
+                                    .long("pos-side")
+                                    .help("持仓方向 (long, short)，开平仓模式下交割/永续必填")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("reduce_only")
+                                    .long("reduce-only")
+                                    .help("只减仓")
+                                    .takes_value(false),
+                            )
+                            .arg(
+                                Arg::new("cl_ord_id")
+                                    .long("cl-ord-id")
+                                    .help("客户自定义订单ID")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("tag")
+                                    .long("tag")
+                                    .help("订单标签")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("tp")
+                                    .long("tp")
+                                    .help("止盈触发价")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("tp_px")
+                                    .long("tp-px")
+                                    .help("止盈委托价，不指定时按市价执行")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("sl")
+                                    .long("sl")
+                                    .help("止损触发价")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("sl_px")
+                                    .long("sl-px")
+                                    .help("止损委托价，不指定时按市价执行")
+                                    .takes_value(true),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("amend")
+                            .about("修改订单的数量或价格")
+                            .arg(
+                                Arg::new("inst_id")
+                                    .help("产品ID，如：BTC-USDT-SWAP")
+                                    .required(true)
+                                    .index(1),
+                            )
+                            .arg(
+                                Arg::new("ord_id")
+                                    .long("ord-id")
+                                    .help("订单ID")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("cl_ord_id")
+                                    .long("cl-ord-id")
+                                    .help("客户自定义订单ID")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("sz")
+                                    .long("sz")
+                                    .help("新的委托数量（含已成交数量）")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("px")
+                                    .long("px")
+                                    .help("新的委托价格")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("cxl_on_fail")
+                                    .long("cxl-on-fail")
+                                    .help("修改失败时自动撤单")
+                                    .takes_value(false),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("batch")
+                            .about("批量下单、改单或撤单（超过20个订单时分批请求）")
+                            .arg(
+                                Arg::new("file")
+                                    .help("JSON文件，内容为下单/改单/撤单请求数组")
+                                    .required(true)
+                                    .index(1),
+                            )
+                            .arg(
+                                Arg::new("action")
+                                    .long("action")
+                                    .help("批量操作 (place, amend, cancel)")
+                                    .default_value("place")
+                                    .takes_value(true),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("cancel")
+                            .about("撤单")
+                            .arg(
+                                Arg::new("inst_id")
+                                    .help("产品ID，如：BTC-USDT-SWAP")
+                                    .required(true)
+                                    .index(1),
+                            )
+                            .arg(
+                                Arg::new("ord_id")
+                                    .long("ord-id")
+                                    .help("订单ID")
+                                    .takes_value(true),
+                            )
+                            .arg(
+                                Arg::new("cl_ord_id")
+                                    .long("cl-ord-id")
+                                    .help("客户自定义订单ID")
+                                    .takes_value(true),
+                            ),
                     ),
             )
             .get_matches();
 
-        // 加载配置
+        // 加载配置，所有命令共用一个客户端
         let config = Config::from_env()?;
+        let client = OkxClient::new(config);
+
+        // 输出时区，同时用于解释不带时区的日期参数
+        let tz: Option<DisplayTz> = matches.value_of("tz").map(str::parse).transpose()?;
+        let parse_time_arg = |value: Option<&str>| -> anyhow::Result<Option<String>> {
+            value
+                .map(|s| parse_time(s, chrono::Utc::now(), tz.unwrap_or(DisplayTz::Utc)))
+                .transpose()
+                .map(|time| time.map(|t| t.timestamp_millis().to_string()))
+        };
 
         match matches.subcommand() {
             Some(("history", sub_matches)) => {
@@ -150,25 +856,108 @@ fn main() -> anyhow::Result<()> {
 
                 // 构建查询参数
                 let params = PositionsHistoryParams {
-                    inst_type: sub_matches.value_of("inst_type").map(|s| s.to_string()),
+                    inst_type: sub_matches.value_of("inst_type").map(str::parse).transpose()?,
                     inst_id: sub_matches.value_of("inst_id").map(|s| s.to_string()),
-                    mgn_mode: sub_matches.value_of("mgn_mode").map(|s| s.to_string()),
-                    close_type: sub_matches.value_of("close_type").map(|s| s.to_string()),
+                    mgn_mode: sub_matches.value_of("mgn_mode").map(str::parse).transpose()?,
+                    close_type: sub_matches.value_of("close_type").map(str::parse).transpose()?,
                     pos_id: sub_matches.value_of("pos_id").map(|s| s.to_string()),
-                    before: sub_matches.value_of("before").map(|s| s.to_string()),
-                    after: sub_matches.value_of("after").map(|s| s.to_string()),
+                    before: parse_time_arg(sub_matches.value_of("before"))?,
+                    after: parse_time_arg(sub_matches.value_of("after"))?,
                     limit: sub_matches.value_of("limit").map(|s| s.to_string()),
                 };
 
-                // 调用API
-                match get_positions_history(&config, &params).await {
-                    Ok(response) => {
-                        println!("查询成功！");
-                        println!("响应数据: {}", serde_json::to_string_pretty(&response)?);
+                if sub_matches.is_present("all") {
+                    // 自动翻页获取全部记录
+                    match client.get_positions_history_all(&params).await {
+                        Ok(records) => {
+                            println!("查询成功！共 {} 条记录", records.len());
+                            println!("响应数据: {}", to_pretty_json(&records, tz)?);
+                        }
+                        Err(e) => {
+                            exit_with_error("查询失败", &e);
+                        }
+                    }
+                } else {
+                    // 调用API
+                    match client.get_positions_history(&params).await {
+                        Ok(response) => {
+                            println!("查询成功！");
+                            println!("响应数据: {}", to_pretty_json(&response, tz)?);
+                        }
+                        Err(e) => {
+                            exit_with_error("查询失败", &e);
+                        }
+                    }
+                }
+            }
+            Some(("positions", sub_matches)) if sub_matches.subcommand_name() == Some("margin") => {
+                let margin_matches = sub_matches.subcommand_matches("margin").unwrap();
+                let (adjustment, adjust_matches) = match margin_matches.subcommand() {
+                    Some(("add", m)) => (MarginAdjustment::Add, m),
+                    Some(("reduce", m)) => (MarginAdjustment::Reduce, m),
+                    _ => {
+                        println!("请指定要执行的保证金命令:");
+                        println!("  add    - 增加逐仓保证金");
+                        println!("  reduce - 减少逐仓保证金");
+                        println!("\n使用 --help 查看详细帮助信息");
+                        return Ok(());
+                    }
+                };
+
+                let request = MarginBalanceRequest {
+                    inst_id: adjust_matches.value_of("inst_id").unwrap_or_default().to_string(),
+                    pos_side: adjust_matches.value_of("pos_side").unwrap_or("net").parse()?,
+                    adjustment,
+                    amt: adjust_matches
+                        .value_of("amt")
+                        .unwrap_or_default()
+                        .parse()
+                        .map_err(|e| anyhow::anyhow!("无效的保证金数量: {}", e))?,
+                    ccy: adjust_matches.value_of("ccy").map(|s| s.to_string()),
+                    auto: adjust_matches.is_present("auto").then_some(true),
+                };
+
+                let before = match client.get_isolated_position(&request.inst_id, &request.pos_side).await {
+                    Ok(position) => position,
+                    Err(e) => {
+                        eprintln!("查询调整前的持仓失败: {}", e);
+                        None
+                    }
+                };
+
+                println!("开始调整逐仓保证金...");
+                match client.adjust_margin_and_refresh(&request).await {
+                    Ok((result, after)) => {
+                        println!(
+                            "调整成功！{} {} {} {}",
+                            result.inst_id,
+                            result.pos_side,
+                            if result.adjustment == MarginAdjustment::Add { "增加" } else { "减少" },
+                            format_decimal(&result.amt)
+                        );
+                        if result.leverage.is_some() {
+                            println!("实际杠杆: {}", format_decimal(&result.leverage));
+                        }
+
+                        let describe = |position: &Position| {
+                            format!(
+                                "保证金 {} | 保证金率 {} | 强平价 {}",
+                                format_decimal(&position.margin),
+                                format_decimal(&position.mgn_ratio),
+                                format_decimal(&position.liq_px)
+                            )
+                        };
+                        if let Some(before) = &before {
+                            println!("调整前: {}", describe(before));
+                        }
+                        match &after {
+                            Ok(Some(after)) => println!("调整后: {}", describe(after)),
+                            Ok(None) => eprintln!("未能查询到调整后的逐仓持仓"),
+                            Err(e) => eprintln!("查询调整后的持仓失败: {}", e),
+                        }
                     }
                     Err(e) => {
-                        eprintln!("查询失败: {}", e);
-                        std::process::exit(1);
+                        exit_with_error("调整失败", &e);
                     }
                 }
             }
@@ -177,7 +966,7 @@ fn main() -> anyhow::Result<()> {
 
                 // 构建查询参数
                 let params = PositionsParams {
-                    inst_type: sub_matches.value_of("inst_type").map(|s| s.to_string()),
+                    inst_type: sub_matches.value_of("inst_type").map(str::parse).transpose()?,
                     inst_id: sub_matches.value_of("inst_id").map(|s| s.to_string()),
                     pos_id: sub_matches.value_of("pos_id").map(|s| s.to_string()),
                 };
@@ -187,7 +976,7 @@ fn main() -> anyhow::Result<()> {
 
                 if is_simple {
                     // 使用简化输出
-                    match get_positions_simplified(&config, &params).await {
+                    match client.get_positions_simplified(&params).await {
                         Ok(positions) => {
                             println!("查询成功！");
                             println!("找到 {} 个持仓", positions.len());
@@ -206,24 +995,22 @@ fn main() -> anyhow::Result<()> {
                                     .iter()
                                     .map(|pos| pos.to_json())
                                     .collect();
-                                println!("{}", serde_json::to_string_pretty(&json_array)?);
+                                println!("{}", to_pretty_json(&json_array, tz)?);
                             }
                         }
                         Err(e) => {
-                            eprintln!("查询失败: {}", e);
-                            std::process::exit(1);
+                            exit_with_error("查询失败", &e);
                         }
                     }
                 } else {
                     // 使用完整输出
-                    match get_positions(&config, &params).await {
+                    match client.get_positions(&params).await {
                         Ok(response) => {
                             println!("查询成功！");
-                            println!("响应数据: {}", serde_json::to_string_pretty(&response)?);
+                            println!("响应数据: {}", to_pretty_json(&response, tz)?);
                         }
                         Err(e) => {
-                            eprintln!("查询失败: {}", e);
-                            std::process::exit(1);
+                            exit_with_error("查询失败", &e);
                         }
                     }
                 }
@@ -233,46 +1020,328 @@ fn main() -> anyhow::Result<()> {
                     Some(("balance", balance_matches)) => {
                         println!("开始查询账户余额...");
                         
-                        match balance_matches.value_of("ccy") {
-                            Some(ccy) => {
-                                // 查询指定币种余额
-                                match get_account_balance_by_currency(&config, ccy).await {
-                                    Ok(response) => {
-                                        println!("查询成功！");
-                                        println!("响应数据: {}", serde_json::to_string_pretty(&response)?);
+                        let format = balance_matches.value_of("format").unwrap_or("json");
+
+                        let result = match balance_matches.value_of("ccy") {
+                            // 查询指定币种余额
+                            Some(ccy) => client.get_account_balance_by_currency(ccy).await,
+                            // 查询所有币种余额
+                            None => client.get_account_balance().await,
+                        };
+
+                        match result {
+                            Ok(response) => {
+                                println!("查询成功！");
+
+                                if format == "table" {
+                                    // 表格格式输出
+                                    for balance in &response.data {
+                                        println!("{}", balance.format_summary());
+                                        println!("\n{}", BalanceDetail::table_header());
+                                        println!("{:-<136}", "");
+                                        for detail in &balance.details {
+                                            println!("{}", detail.format_display());
+                                        }
+                                    }
+                                } else {
+                                    println!("响应数据: {}", to_pretty_json(&response, tz)?);
+                                }
+                            }
+                            Err(e) => {
+                                exit_with_error("查询失败", &e);
+                            }
+                        }
+                    }
+                    Some(("config", config_matches)) => {
+                        println!("开始查询账户配置...");
+                        let format = config_matches.value_of("format").unwrap_or("json");
+
+                        match client.get_account_config().await {
+                            Ok(response) => {
+                                println!("查询成功！");
+
+                                if format == "table" {
+                                    for config in &response.data {
+                                        println!("\n{}", config.format_summary());
                                     }
-                                    Err(e) => {
-                                        eprintln!("查询失败: {}", e);
-                                        std::process::exit(1);
+                                } else {
+                                    println!("响应数据: {}", to_pretty_json(&response, tz)?);
+                                }
+                            }
+                            Err(e) => {
+                                exit_with_error("查询失败", &e);
+                            }
+                        }
+                    }
+                    Some(("bills", bills_matches)) => {
+                        println!("开始查询账单流水...");
+
+                        let source = if bills_matches.is_present("archive") {
+                            BillsSource::Archive
+                        } else {
+                            BillsSource::Recent
+                        };
+                        let params = BillsParams {
+                            inst_type: bills_matches.value_of("inst_type").map(str::parse).transpose()?,
+                            inst_id: bills_matches.value_of("inst_id").map(|s| s.to_string()),
+                            ccy: bills_matches.value_of("ccy").map(|s| s.to_string()),
+                            bill_type: bills_matches.value_of("type").map(str::parse).transpose()?,
+                            sub_type: bills_matches.value_of("sub_type").map(str::parse).transpose()?,
+                            begin: parse_time_arg(bills_matches.value_of("begin"))?,
+                            end: parse_time_arg(bills_matches.value_of("end"))?,
+                            limit: bills_matches.value_of("limit").map(|s| s.to_string()),
+                            ..Default::default()
+                        };
+                        let format = bills_matches.value_of("format").unwrap_or("json");
+
+                        let result = if bills_matches.is_present("all") {
+                            client.get_bills_all(source, &params).await
+                        } else {
+                            client.get_bills_from(source, &params).await.map(|r| r.data)
+                        };
+
+                        match result {
+                            Ok(bills) => {
+                                println!("查询成功！共 {} 条账单", bills.len());
+
+                                if format == "table" {
+                                    println!("\n{}", Bill::table_header());
+                                    println!("{:-<171}", "");
+                                    for bill in &bills {
+                                        println!("{}", bill.format_display(tz.unwrap_or(DisplayTz::Utc)));
                                     }
+                                } else {
+                                    println!("响应数据: {}", to_pretty_json(&bills, tz)?);
+                                }
+                            }
+                            Err(e) => {
+                                exit_with_error("查询失败", &e);
+                            }
+                        }
+                    }
+                    Some(("leverage", leverage_matches)) => match leverage_matches.subcommand() {
+                        Some(("get", get_matches)) => {
+                            println!("开始查询杠杆倍数...");
+
+                            let params = LeverageInfoParams {
+                                inst_id: get_matches.value_of("inst_id").map(|s| s.to_string()),
+                                ccy: get_matches.value_of("ccy").map(|s| s.to_string()),
+                                mgn_mode: get_matches.value_of("mgn_mode").unwrap_or_default().parse()?,
+                            };
+
+                            match client.get_leverage_info(&params).await {
+                                Ok(response) => {
+                                    println!("查询成功！");
+                                    print_leverage_table(&response.data);
+                                }
+                                Err(e) => {
+                                    exit_with_error("查询失败", &e);
                                 }
                             }
-                            None => {
-                                // 查询所有币种余额
-                                match get_account_balance(&config).await {
-                                    Ok(response) => {
-                                        println!("查询成功！");
-                                        println!("响应数据: {}", serde_json::to_string_pretty(&response)?);
+                        }
+                        Some(("set", set_matches)) => {
+                            let request = SetLeverageRequest {
+                                inst_id: set_matches.value_of("inst_id").map(|s| s.to_string()),
+                                ccy: set_matches.value_of("ccy").map(|s| s.to_string()),
+                                lever: set_matches
+                                    .value_of("lever")
+                                    .unwrap_or_default()
+                                    .parse()
+                                    .map_err(|e| anyhow::anyhow!("无效的杠杆倍数: {}", e))?,
+                                mgn_mode: set_matches.value_of("mgn_mode").unwrap_or_default().parse()?,
+                                pos_side: set_matches.value_of("pos_side").map(str::parse).transpose()?,
+                            };
+
+                            // 调整前的杠杆倍数，查询失败不影响设置
+                            let before = match client.get_leverage_info(&request.info_params()).await {
+                                Ok(response) => Some(response.data),
+                                Err(e) => {
+                                    eprintln!("查询当前杠杆倍数失败: {}", e);
+                                    None
+                                }
+                            };
+
+                            println!("开始设置杠杆倍数...");
+                            match client.set_leverage(&request).await {
+                                Ok(_) => {
+                                    println!("设置成功！");
+                                    if let Some(before) = before {
+                                        println!("\n调整前:");
+                                        print_leverage_table(&before);
+                                    }
+                                    match client.get_leverage_info(&request.info_params()).await {
+                                        Ok(after) => {
+                                            println!("\n调整后:");
+                                            print_leverage_table(&after.data);
+                                        }
+                                        Err(e) => eprintln!("查询调整后的杠杆倍数失败: {}", e),
                                     }
-                                    Err(e) => {
-                                        eprintln!("查询失败: {}", e);
-                                        std::process::exit(1);
+                                }
+                                Err(e) => {
+                                    exit_with_error("设置失败", &e);
+                                }
+                            }
+                        }
+                        _ => {
+                            println!("请指定要执行的杠杆命令:");
+                            println!("  get - 查询杠杆倍数");
+                            println!("  set - 设置杠杆倍数");
+                            println!("\n使用 --help 查看详细帮助信息");
+                        }
+                    },
+                    Some(("limits", limits_matches)) => {
+                        println!("开始查询下单限额...");
+
+                        let params = MaxSizeParams {
+                            inst_id: limits_matches.value_of("inst_id").unwrap_or_default().to_string(),
+                            td_mode: limits_matches.value_of("td_mode").unwrap_or_default().parse()?,
+                            ccy: limits_matches.value_of("ccy").map(|s| s.to_string()),
+                            px: limits_matches
+                                .value_of("px")
+                                .map(str::parse)
+                                .transpose()
+                                .map_err(|e| anyhow::anyhow!("无效的价格: {}", e))?,
+                        };
+
+                        match client.get_order_limits(&params).await {
+                            Ok(limits) => {
+                                println!("查询成功！");
+                                println!("\n{}", OrderLimits::table_header());
+                                println!("{:-<105}", "");
+                                for limit in &limits {
+                                    println!("{}", limit.format_display());
+                                }
+                            }
+                            Err(e) => {
+                                exit_with_error("查询失败", &e);
+                            }
+                        }
+
+                        if let Some(ccy) = params.ccy.as_deref() {
+                            match client.get_max_withdrawal(Some(ccy)).await {
+                                Ok(response) => {
+                                    for withdrawal in &response.data {
+                                        println!(
+                                            "\n{} 最大可转余额: {} (含借币: {})",
+                                            withdrawal.ccy,
+                                            format_decimal(&withdrawal.max_wd),
+                                            format_decimal(&withdrawal.max_wd_ex)
+                                        );
                                     }
                                 }
+                                // 限额已经输出，余额查询失败只提示，不影响命令结果
+                                Err(e) => eprintln!("\n查询最大可转余额失败: {}", e),
                             }
                         }
                     }
-                    Some(("config", _)) => {
-                        println!("开始查询账户配置...");
-                        
-                        match get_account_config(&config).await {
+                    Some(("fees", fees_matches)) => {
+                        println!("开始查询手续费费率...");
+
+                        let inst_type: InstType = fees_matches.value_of("inst_type").unwrap_or_default().parse()?;
+                        let inst_id = fees_matches.value_of("inst_id");
+                        let params = TradeFeeParams {
+                            // 只有币币和币币杠杆按产品ID查询
+                            inst_id: inst_id
+                                .filter(|_| matches!(inst_type, InstType::Spot | InstType::Margin))
+                                .map(|s| s.to_string()),
+                            inst_family: fees_matches.value_of("inst_family").map(|s| s.to_string()),
+                            inst_type,
+                        };
+                        let notional: Option<Decimal> = fees_matches
+                            .value_of("notional")
+                            .map(str::parse)
+                            .transpose()
+                            .map_err(|e| anyhow::anyhow!("无效的名义价值: {}", e))?;
+                        let open: ExecType = fees_matches.value_of("open").unwrap_or("taker").parse()?;
+                        let close: ExecType = fees_matches.value_of("close").unwrap_or("taker").parse()?;
+
+                        let cache = TradeFeeCache::new(client.clone());
+                        let fee = match cache.get(&params).await {
+                            Ok(fee) => fee,
+                            Err(e) => {
+                                exit_with_error("查询失败", &e);
+                            }
+                        };
+                        println!("查询成功！");
+                        println!("{}", fee.format_summary());
+
+                        if let Some(notional) = notional {
+                            let inst_id = inst_id.ok_or_else(|| anyhow::anyhow!("估算手续费需要指定 --inst-id"))?;
+                            let rates = cache.rates_for(&params, inst_id).await?;
+                            let estimate = rates.estimate_round_trip(notional, &open, &close);
+                            println!(
+                                "\n{} 名义价值 {} 的往返手续费估算（负数为支出）:",
+                                inst_id, notional
+                            );
+                            println!("  开仓 ({}, 费率 {}): {}", open, rates.rate(&open), estimate.open_fee.normalize());
+                            println!("  平仓 ({}, 费率 {}): {}", close, rates.rate(&close), estimate.close_fee.normalize());
+                            println!("  合计: {}", estimate.total.normalize());
+                        }
+                    }
+                    Some(("set-position-mode", mode_matches)) => {
+                        let pos_mode: PositionMode = mode_matches.value_of("mode").unwrap_or_default().parse()?;
+
+                        // 已经是目标模式时无需切换
+                        if let Ok(config) = client.get_account_config().await {
+                            if config.data.iter().any(|c| c.pos_mode == pos_mode) {
+                                println!("当前已是{} ({})，无需切换", pos_mode.description(), pos_mode);
+                                return Ok(());
+                            }
+                        }
+
+                        println!("检查交割、永续持仓和挂单...");
+                        let blockers = match client.check_position_mode_switch().await {
+                            Ok(blockers) => blockers,
+                            Err(e) => {
+                                exit_with_error("切换前检查失败", &e);
+                            }
+                        };
+                        if !blockers.is_clear() {
+                            eprintln!("无法切换持仓模式:\n{}", blockers.explain());
+                            std::process::exit(1);
+                        }
+
+                        match client.set_position_mode(&pos_mode).await {
                             Ok(response) => {
-                                println!("查询成功！");
-                                println!("响应数据: {}", serde_json::to_string_pretty(&response)?);
+                                for result in &response.data {
+                                    println!("切换成功！当前持仓模式: {} ({})", result.pos_mode.description(), result.pos_mode);
+                                }
+                            }
+                            Err(e) => {
+                                exit_with_error("切换失败", &e);
+                            }
+                        }
+                    }
+                    Some(("set-level", level_matches)) => {
+                        let acct_lv: AccountLevel = level_matches.value_of("level").unwrap_or_default().parse()?;
+
+                        if let Ok(config) = client.get_account_config().await {
+                            if config.data.iter().any(|c| c.acct_lv == acct_lv) {
+                                println!("当前已是{} (acctLv={})，无需切换", acct_lv.description(), acct_lv);
+                                return Ok(());
+                            }
+                        }
+
+                        println!("检查持仓和挂单...");
+                        let blockers = match client.check_account_level_switch().await {
+                            Ok(blockers) => blockers,
+                            Err(e) => {
+                                exit_with_error("切换前检查失败", &e);
+                            }
+                        };
+                        if !blockers.is_clear() {
+                            eprintln!("无法切换账户模式:\n{}", blockers.explain());
+                            std::process::exit(1);
+                        }
+
+                        match client.set_account_level(&acct_lv).await {
+                            Ok(response) => {
+                                for result in &response.data {
+                                    println!("切换成功！当前账户模式: {} (acctLv={})", result.acct_lv.description(), result.acct_lv);
+                                }
                             }
                             Err(e) => {
-                                eprintln!("查询失败: {}", e);
-                                std::process::exit(1);
+                                exit_with_error("切换失败", &e);
                             }
                         }
                     }
@@ -280,7 +1349,398 @@ fn main() -> anyhow::Result<()> {
                         println!("请指定要执行的账户查询命令:");
                         println!("  balance  - 查询账户余额");
                         println!("  config   - 查询账户配置");
+                        println!("  bills    - 查询账单流水");
+                        println!("  leverage - 查询或设置杠杆倍数");
+                        println!("  limits   - 查询下单限额");
+                        println!("  fees     - 查询手续费费率");
+                        println!("  set-position-mode - 切换持仓模式");
+                        println!("  set-level         - 切换账户模式");
+                        println!("\n使用 --help 查看详细帮助信息");
+                    }
+                }
+            }
+            Some(("borrow", sub_matches)) => match sub_matches.subcommand() {
+                Some(("interest", interest_matches)) => {
+                    println!("开始查询计息记录...");
+
+                    let display_tz = tz.unwrap_or(DisplayTz::Utc);
+                    let params = InterestAccruedParams {
+                        loan_type: interest_matches.value_of("type").map(|s| s.to_string()),
+                        ccy: interest_matches.value_of("ccy").map(|s| s.to_string()),
+                        after: parse_time_arg(interest_matches.value_of("end"))?,
+                        before: parse_time_arg(interest_matches.value_of("begin"))?,
+                        ..Default::default()
+                    };
+
+                    match client.get_interest_accrued_all(&params).await {
+                        Ok(records) => {
+                            println!("查询成功！共 {} 条计息记录", records.len());
+
+                            let daily = daily_interest(&records, display_tz);
+                            println!("\n{:<10} | {:<8} | {:<20}", "Date", "Ccy", "Interest");
+                            println!("{:-<44}", "");
+                            for ((date, ccy), interest) in &daily {
+                                println!("{:<10} | {:<8} | {:<20}", date, ccy, interest.normalize());
+                            }
+
+                            // 各币种合计
+                            let mut totals: std::collections::BTreeMap<&str, Decimal> = Default::default();
+                            for ((_, ccy), interest) in &daily {
+                                *totals.entry(ccy.as_str()).or_default() += *interest;
+                            }
+                            println!("{:-<44}", "");
+                            for (ccy, total) in totals {
+                                println!("{:<10} | {:<8} | {:<20}", "合计", ccy, total.normalize());
+                            }
+                        }
+                        Err(e) => {
+                            exit_with_error("查询失败", &e);
+                        }
+                    }
+                }
+                Some(("rates", rates_matches)) => {
+                    println!("开始查询借币利率...");
+                    let ccy = rates_matches.value_of("ccy");
+
+                    match client.get_interest_limits(ccy).await {
+                        Ok(response) => {
+                            println!("查询成功！");
+                            for limits in &response.data {
+                                println!(
+                                    "当前负债: {} USD | 已计利息: {} USD",
+                                    format_decimal(&limits.debt),
+                                    format_decimal(&limits.interest)
+                                );
+                                println!(
+                                    "\n{:<8} | {:<14} | {:<16} | {:<16} | {:<16}",
+                                    "Ccy", "DailyRate", "LoanQuota", "UsedLmt", "SurplusLmt"
+                                );
+                                println!("{:-<82}", "");
+                                for record in &limits.records {
+                                    println!(
+                                        "{:<8} | {:<14} | {:<16} | {:<16} | {:<16}",
+                                        record.ccy,
+                                        format_decimal(&record.rate),
+                                        format_decimal(&record.loan_quota),
+                                        format_decimal(&record.used_lmt),
+                                        format_decimal(&record.surplus_lmt)
+                                    );
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            exit_with_error("查询失败", &e);
+                        }
+                    }
+                }
+                Some((side @ ("borrow" | "repay"), manual_matches)) => {
+                    let request = ManualBorrowRepay {
+                        ccy: manual_matches.value_of("ccy").unwrap_or_default().to_string(),
+                        side: if side == "borrow" { BorrowSide::Borrow } else { BorrowSide::Repay },
+                        amt: manual_matches
+                            .value_of("amt")
+                            .unwrap_or_default()
+                            .parse()
+                            .map_err(|e| anyhow::anyhow!("无效的数量: {}", e))?,
+                    };
+
+                    match client.spot_manual_borrow_repay(&request).await {
+                        Ok(response) => {
+                            for result in &response.data {
+                                println!(
+                                    "{}成功！{} {}",
+                                    if result.side == BorrowSide::Borrow { "借币" } else { "还币" },
+                                    result.amt.normalize(),
+                                    result.ccy
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            exit_with_error("操作失败", &e);
+                        }
+                    }
+                }
+                _ => {
+                    println!("请指定要执行的借币命令:");
+                    println!("  interest - 按日汇总利息支出");
+                    println!("  rates    - 查询借币利率与限额");
+                    println!("  borrow   - 手动借币");
+                    println!("  repay    - 手动还币");
+                    println!("\n使用 --help 查看详细帮助信息");
+                }
+            },
+            Some(("risk", risk_matches)) => {
+                println!("开始查询账户风险...");
+
+                // 账户配置用于确定默认的希腊字母展示方式和是否查询风险状态，查询失败时继续输出其余部分
+                let config = match client.get_account_config().await {
+                    Ok(response) => response.data.into_iter().next(),
+                    Err(e) => {
+                        eprintln!("查询账户配置失败，按PA展示希腊字母且跳过风险状态: {}", e);
+                        None
+                    }
+                };
+                let pa = match risk_matches.value_of("greeks_type") {
+                    Some(greeks_type) => match greeks_type.to_ascii_uppercase().as_str() {
+                        "PA" => true,
+                        "BS" => false,
+                        other => anyhow::bail!("无效的希腊字母展示方式: {} (可选: PA, BS)", other),
+                    },
+                    None => config.as_ref().is_none_or(|c| c.greeks_type != "BS"),
+                };
+
+                match client.get_account_balance().await {
+                    Ok(response) => {
+                        for balance in &response.data {
+                            println!("\n{}", balance.format_summary());
+                        }
+                    }
+                    Err(e) => {
+                        exit_with_error("查询账户余额失败", &e);
+                    }
+                }
+
+                match client.get_greeks(risk_matches.value_of("ccy")).await {
+                    Ok(response) => {
+                        println!("\n希腊字母 ({}):", if pa { "PA 币本位" } else { "BS 美元本位" });
+                        println!("{}", Greeks::table_header());
+                        println!("{:-<92}", "");
+                        for greeks in &response.data {
+                            println!("{}", greeks.format_display(pa));
+                        }
+                    }
+                    Err(e) => {
+                        exit_with_error("查询希腊字母失败", &e);
+                    }
+                }
+
+                // 风险状态仅适用于组合保证金模式
+                if config.as_ref().is_some_and(|c| c.acct_lv == AccountLevel::Portfolio) {
+                    match client.get_risk_state().await {
+                        Ok(response) => {
+                            for state in &response.data {
+                                if state.at_risk {
+                                    println!("\n风险状态: 处于自动借币风险");
+                                    println!("  衍生品风险单元: {}", state.at_risk_idx.join(", "));
+                                    println!("  币币杠杆风险单元: {}", state.at_risk_mgn.join(", "));
+                                } else {
+                                    println!("\n风险状态: 正常");
+                                }
+                            }
+                        }
+                        Err(e) => eprintln!("查询风险状态失败: {}", e),
+                    }
+                }
+
+                if risk_matches.is_present("positions") {
+                    match client.get_account_position_risk(None).await {
+                        Ok(response) => {
+                            for risk in &response.data {
+                                println!("\n持仓风险 (有效保证金 {}):", format_decimal(&risk.adj_eq));
+                                for position in &risk.pos_data {
+                                    println!(
+                                        "  {:<20} {:<8} {:<6} 持仓 {:<12} 名义价值 {} USD",
+                                        position.inst_id,
+                                        position.mgn_mode,
+                                        position.pos_side.as_ref().map(|s| s.as_str()).unwrap_or(""),
+                                        format_decimal(&position.pos),
+                                        format_decimal(&position.notional_usd)
+                                    );
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            exit_with_error("查询持仓风险失败", &e);
+                        }
+                    }
+                }
+            }
+            Some(("simulate", simulate_matches)) => {
+                let path = simulate_matches.value_of("file").unwrap_or_default();
+                let content = std::fs::read_to_string(path)
+                    .map_err(|e| anyhow::anyhow!("读取文件 {} 失败: {}", path, e))?;
+                let mut request = PositionBuilderRequest::from_file_content(path, &content)
+                    .map_err(|e| anyhow::anyhow!("解析文件 {} 失败: {}", path, e))?;
+
+                if simulate_matches.is_present("include_real") {
+                    request.incl_real_pos_and_eq = true;
+                }
+                if simulate_matches.is_present("seed") {
+                    match client.get_positions(&PositionsParams::default()).await {
+                        Ok(response) => {
+                            let seeded = request.seed_from_positions(&response.data);
+                            println!("已加入 {} 个当前持仓", seeded);
+                        }
+                        Err(e) => {
+                            exit_with_error("查询当前持仓失败", &e);
+                        }
+                    }
+                }
+
+                println!("开始模拟 {} 个持仓、{} 项资产...", request.sim_pos.len(), request.sim_asset.len());
+                match client.build_positions(&request).await {
+                    Ok(response) => {
+                        println!("模拟成功！");
+                        for result in &response.data {
+                            println!("\n{}", result.format_summary());
+                            println!("\n{}", RiskUnit::table_header());
+                            println!("{:-<88}", "");
+                            for unit in &result.risk_unit_data {
+                                println!("{}", unit.format_display());
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        exit_with_error("模拟失败", &e);
+                    }
+                }
+            }
+            Some(("order", sub_matches)) => {
+                let parse_decimal = |value: Option<&str>, label: &str| -> anyhow::Result<Option<Decimal>> {
+                    value
+                        .map(|s| s.parse().map_err(|e| anyhow::anyhow!("无效的{}: {} ({})", label, s, e)))
+                        .transpose()
+                };
+
+                let outcome = match sub_matches.subcommand() {
+                    Some(("place", place_matches)) => {
+                        let px = parse_decimal(place_matches.value_of("px"), "委托价格")?;
+                        let ord_type = match place_matches.value_of("type") {
+                            Some(ord_type) => ord_type.parse()?,
+                            None if px.is_some() => OrdType::Limit,
+                            None => OrdType::Market,
+                        };
+                        let mut request = PlaceOrderRequest::new(
+                            place_matches.value_of("inst_id").unwrap_or_default(),
+                            place_matches.value_of("td_mode").unwrap_or_default().parse()?,
+                            place_matches.value_of("side").unwrap_or_default().parse()?,
+                            ord_type,
+                            parse_decimal(place_matches.value_of("sz"), "委托数量")?.unwrap_or_default(),
+                        );
+                        request.px = px;
+                        request.pos_side = place_matches.value_of("pos_side").map(str::parse).transpose()?;
+                        request.reduce_only = place_matches.is_present("reduce_only").then_some(true);
+                        request.cl_ord_id = place_matches.value_of("cl_ord_id").map(|s| s.to_string());
+                        request.tag = place_matches.value_of("tag").map(|s| s.to_string());
+
+                        let mut algo = AttachAlgoOrder::default();
+                        if let Some(tp) = parse_decimal(place_matches.value_of("tp"), "止盈触发价")? {
+                            algo = algo.with_take_profit(tp, parse_decimal(place_matches.value_of("tp_px"), "止盈委托价")?);
+                        }
+                        if let Some(sl) = parse_decimal(place_matches.value_of("sl"), "止损触发价")? {
+                            algo = algo.with_stop_loss(sl, parse_decimal(place_matches.value_of("sl_px"), "止损委托价")?);
+                        }
+                        if algo != AttachAlgoOrder::default() {
+                            request.attach_algo_ords.push(algo);
+                        }
+
+                        println!(
+                            "下单: {} {} {} {} {}",
+                            request.inst_id,
+                            request.side,
+                            request.ord_type,
+                            request.sz,
+                            format_decimal(&request.px)
+                        );
+                        client.place_order(&request).await
+                    }
+                    Some(("amend", amend_matches)) => {
+                        let request = AmendOrderRequest {
+                            inst_id: amend_matches.value_of("inst_id").unwrap_or_default().to_string(),
+                            ord_id: amend_matches.value_of("ord_id").map(|s| s.to_string()),
+                            cl_ord_id: amend_matches.value_of("cl_ord_id").map(|s| s.to_string()),
+                            req_id: None,
+                            new_sz: parse_decimal(amend_matches.value_of("sz"), "委托数量")?,
+                            new_px: parse_decimal(amend_matches.value_of("px"), "委托价格")?,
+                            cxl_on_fail: amend_matches.is_present("cxl_on_fail").then_some(true),
+                        };
+                        client.amend_order(&request).await
+                    }
+                    Some(("cancel", cancel_matches)) => {
+                        let request = CancelOrderRequest {
+                            inst_id: cancel_matches.value_of("inst_id").unwrap_or_default().to_string(),
+                            ord_id: cancel_matches.value_of("ord_id").map(|s| s.to_string()),
+                            cl_ord_id: cancel_matches.value_of("cl_ord_id").map(|s| s.to_string()),
+                        };
+                        client.cancel_order(&request).await
+                    }
+                    Some(("batch", batch_matches)) => {
+                        let path = batch_matches.value_of("file").unwrap_or_default();
+                        let content = std::fs::read_to_string(path)
+                            .map_err(|e| anyhow::anyhow!("读取文件 {} 失败: {}", path, e))?;
+                        let parse_error = |e: serde_json::Error| anyhow::anyhow!("解析文件 {} 失败: {}", path, e);
+
+                        let (inst_ids, results) = match batch_matches.value_of("action").unwrap_or_default() {
+                            "place" => {
+                                let requests: Vec<PlaceOrderRequest> = serde_json::from_str(&content).map_err(parse_error)?;
+                                println!("开始批量下单，共 {} 个订单...", requests.len());
+                                let inst_ids: Vec<String> = requests.iter().map(|r| r.inst_id.clone()).collect();
+                                (inst_ids, client.place_orders(&requests).await)
+                            }
+                            "amend" => {
+                                let requests: Vec<AmendOrderRequest> = serde_json::from_str(&content).map_err(parse_error)?;
+                                println!("开始批量改单，共 {} 个订单...", requests.len());
+                                let inst_ids: Vec<String> = requests.iter().map(|r| r.inst_id.clone()).collect();
+                                (inst_ids, client.amend_orders(&requests).await)
+                            }
+                            "cancel" => {
+                                let requests: Vec<CancelOrderRequest> = serde_json::from_str(&content).map_err(parse_error)?;
+                                println!("开始批量撤单，共 {} 个订单...", requests.len());
+                                let inst_ids: Vec<String> = requests.iter().map(|r| r.inst_id.clone()).collect();
+                                (inst_ids, client.cancel_orders(&requests).await)
+                            }
+                            other => anyhow::bail!("无效的批量操作: {} (可选: place, amend, cancel)", other),
+                        };
+
+                        println!("\n{:<4} | {:<20} | Result", "#", "InstId");
+                        println!("{:-<60}", "");
+                        let mut failed = 0;
+                        for (index, (inst_id, result)) in inst_ids.iter().zip(&results).enumerate() {
+                            let text = match result {
+                                Ok(outcome) => {
+                                    if !outcome.is_accepted() {
+                                        failed += 1;
+                                    }
+                                    outcome.to_string()
+                                }
+                                Err(e) => {
+                                    failed += 1;
+                                    format!("请求失败: {}", e)
+                                }
+                            };
+                            println!("{:<4} | {:<20} | {}", index, inst_id, text);
+                        }
+                        println!("\n成功 {} 个，失败 {} 个", results.len() - failed, failed);
+                        // 请求失败的订单可能已被交易所处理，重试前需要核对
+                        if results.iter().any(|result| result.is_err()) {
+                            eprintln!("请求失败的订单状态未知，重试前请先查询未成交订单 (orders-pending) 核对");
+                        }
+
+                        if failed > 0 {
+                            std::process::exit(1);
+                        }
+                        return Ok(());
+                    }
+                    _ => {
+                        println!("请指定要执行的订单命令:");
+                        println!("  place  - 下单");
+                        println!("  amend  - 修改订单");
+                        println!("  cancel - 撤单");
+                        println!("  batch  - 批量下单、改单或撤单");
                         println!("\n使用 --help 查看详细帮助信息");
+                        return Ok(());
+                    }
+                };
+
+                match outcome {
+                    Ok(outcome) => {
+                        println!("{}", outcome);
+                        if !outcome.is_accepted() {
+                            std::process::exit(1);
+                        }
+                    }
+                    Err(e) => {
+                        exit_with_error("请求失败", &e);
                     }
                 }
             }
@@ -289,6 +1749,10 @@ fn main() -> anyhow::Result<()> {
                 println!("  history   - 查询历史持仓信息");
                 println!("  positions - 查询当前持仓信息");
                 println!("  account   - 查询账户信息");
+                println!("  borrow    - 借币利息、利率与手动借还");
+                println!("  risk      - 查询账户风险");
+                println!("  simulate  - 模拟假设持仓的保证金");
+                println!("  order     - 下单、改单、撤单及批量操作");
                 println!("\n使用 --help 查看详细帮助信息");
             }
         }
@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// 单页最大返回数量
+pub const MAX_PAGE_LIMIT: usize = 100;
+
+/// 解析请求中的 `limit` 参数，无效或未填时取最大值，并限制在 1..=100 之间
+pub fn page_limit(limit: Option<&str>) -> usize {
+    limit
+        .and_then(|limit| limit.parse().ok())
+        .unwrap_or(MAX_PAGE_LIMIT)
+        .clamp(1, MAX_PAGE_LIMIT)
+}
+
+/// 按毫秒时间戳游标向更早记录翻页的状态
+///
+/// 从 `after`（为空则从最新记录）开始，直到某页不足 `limit` 条或越过 `before` 时间下限。
+/// 下一页的游标取本页最旧记录的时间戳 + 1，以免同一毫秒内未返回的记录被跳过，
+/// 重复返回的边界记录按调用方提供的去重键过滤。
+/// 历史持仓和计息记录的分页查询共用此逻辑。
+#[derive(Debug)]
+pub struct TsCursor<K> {
+    limit: usize,
+    lower_bound: Option<i64>,
+    cursor: Option<i64>,
+    boundary: HashSet<(K, i64)>,
+    done: bool,
+}
+
+impl<K: Eq + Hash> TsCursor<K> {
+    /// 根据请求中的 `limit`、`before`、`after` 参数创建
+    pub fn new(limit: Option<&str>, before: Option<&str>, after: Option<&str>) -> Self {
+        Self {
+            limit: page_limit(limit),
+            lower_bound: before.and_then(|before| before.parse().ok()),
+            cursor: after.and_then(|after| after.parse().ok()),
+            boundary: HashSet::new(),
+            done: false,
+        }
+    }
+
+    /// 每页请求的数量
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// 下一页请求的 `after` 参数
+    pub fn cursor(&self) -> Option<i64> {
+        self.cursor
+    }
+
+    /// 是否已没有更多数据
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// 处理一页原始数据：更新游标和结束状态，返回去重并过滤时间下限后的记录
+    ///
+    /// `key` 返回记录的去重键和毫秒时间戳。
+    pub fn advance<T>(&mut self, page: Vec<T>, key: impl Fn(&T) -> (K, i64)) -> Vec<T> {
+        if page.len() < self.limit {
+            self.done = true;
+        }
+
+        let oldest = match page.iter().map(|record| key(record).1).min() {
+            Some(oldest) => oldest,
+            None => {
+                self.done = true;
+                return Vec::new();
+            }
+        };
+
+        // 去掉上一页已返回的边界记录，并记下本页最旧时间点上的记录
+        let previous = std::mem::take(&mut self.boundary);
+        let records: Vec<T> = page
+            .into_iter()
+            .filter(|record| !previous.contains(&key(record)))
+            .collect();
+        self.boundary = previous
+            .into_iter()
+            .chain(records.iter().map(&key))
+            .filter(|(_, ts)| *ts == oldest)
+            .collect();
+
+        let lower_bound = self.lower_bound;
+        let records: Vec<T> = records
+            .into_iter()
+            .filter(|record| lower_bound.is_none_or(|bound| key(record).1 > bound))
+            .collect();
+
+        // 整页都是重复的边界记录时，跳过该毫秒避免死循环
+        self.cursor = Some(if records.is_empty() {
+            oldest
+        } else {
+            oldest + 1
+        });
+
+        if lower_bound.is_some_and(|bound| oldest <= bound) {
+            self.done = true;
+        }
+
+        records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(record: &(&'static str, i64)) -> (&'static str, i64) {
+        *record
+    }
+
+    fn ids(records: &[(&'static str, i64)]) -> Vec<&'static str> {
+        records.iter().map(|(id, _)| *id).collect()
+    }
+
+    #[test]
+    fn test_cursor_follows_oldest_and_dedups_boundary() {
+        let mut cursor = TsCursor::new(Some("3"), None, None);
+
+        let page = cursor.advance(vec![("a", 300), ("b", 200), ("c", 100)], key);
+        assert_eq!(ids(&page), vec!["a", "b", "c"]);
+        assert_eq!(cursor.cursor(), Some(101));
+        assert!(!cursor.is_done());
+
+        // 下一页从 ts <= 100 开始，c 会被再次返回
+        let page = cursor.advance(vec![("c", 100), ("d", 100), ("e", 50)], key);
+        assert_eq!(ids(&page), vec!["d", "e"]);
+        assert_eq!(cursor.cursor(), Some(51));
+
+        let page = cursor.advance(vec![("e", 50)], key);
+        assert!(page.is_empty());
+        assert!(cursor.is_done());
+    }
+
+    #[test]
+    fn test_cursor_skips_millisecond_when_page_is_all_duplicates() {
+        let mut cursor = TsCursor::new(Some("2"), None, None);
+
+        cursor.advance(vec![("a", 100), ("b", 100)], key);
+        assert_eq!(cursor.cursor(), Some(101));
+
+        let page = cursor.advance(vec![("a", 100), ("b", 100)], key);
+        assert!(page.is_empty());
+        assert_eq!(cursor.cursor(), Some(100));
+        assert!(!cursor.is_done());
+    }
+
+    #[test]
+    fn test_cursor_stops_at_lower_bound() {
+        let mut cursor = TsCursor::new(Some("3"), Some("150"), None);
+
+        let page = cursor.advance(vec![("a", 300), ("b", 200), ("c", 100)], key);
+        assert_eq!(ids(&page), vec!["a", "b"]);
+        assert!(cursor.is_done());
+    }
+
+    #[test]
+    fn test_cursor_params() {
+        let cursor: TsCursor<()> = TsCursor::new(Some("500"), None, Some("1700000000000"));
+        assert_eq!(cursor.limit(), MAX_PAGE_LIMIT);
+        assert_eq!(cursor.cursor(), Some(1700000000000));
+
+        assert_eq!(page_limit(None), MAX_PAGE_LIMIT);
+        assert_eq!(page_limit(Some("abc")), MAX_PAGE_LIMIT);
+        assert_eq!(page_limit(Some("0")), 1);
+        assert_eq!(page_limit(Some("20")), 20);
+    }
+}
@@ -0,0 +1,350 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::OkxClient,
+    error::OkxError,
+    time::opt_timestamp_ms,
+    types::{
+        decimal, format_decimal, opt_enum, AccountLevel, ApiResponse, InstType, PosSide, Position,
+    },
+};
+
+/// 模拟持仓
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedPosition {
+    /// 产品ID，如 `BTC-USDT-SWAP`
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    /// 持仓数量，负数表示空头
+    pub pos: Decimal,
+    /// 开仓均价
+    #[serde(rename = "avgPx")]
+    pub avg_px: Decimal,
+    /// 杠杆倍数，仅适用于合约模式和跨币种保证金模式
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub lever: Option<Decimal>,
+}
+
+impl SimulatedPosition {
+    /// 由当前持仓生成模拟持仓，开平仓模式的空头转换为负数持仓
+    ///
+    /// 缺少持仓数量或均价时返回 `None`。
+    pub fn from_position(position: &Position) -> Option<Self> {
+        let pos = position.pos?;
+        let pos = match position.pos_side {
+            PosSide::Short => -pos.abs(),
+            _ => pos,
+        };
+        Some(Self {
+            inst_id: position.inst_id.clone(),
+            pos,
+            avg_px: position.avg_px?,
+            lever: position.lever,
+        })
+    }
+}
+
+/// 模拟资产
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedAsset {
+    /// 币种
+    pub ccy: String,
+    /// 数量，负数表示减少
+    pub amt: Decimal,
+}
+
+/// 持仓构建器请求
+///
+/// 可以直接从JSON或YAML文件读取，`positions`/`assets` 是 `simPos`/`simAsset` 的别名。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PositionBuilderRequest {
+    /// 模拟的账户模式，不填时使用当前账户模式
+    #[serde(rename = "acctLv", skip_serializing_if = "Option::is_none", default)]
+    pub acct_lv: Option<AccountLevel>,
+    /// 是否计入真实持仓和资产
+    #[serde(rename = "inclRealPosAndEq", default)]
+    pub incl_real_pos_and_eq: bool,
+    /// 模拟持仓
+    #[serde(rename = "simPos", alias = "positions", default)]
+    pub sim_pos: Vec<SimulatedPosition>,
+    /// 模拟资产
+    #[serde(rename = "simAsset", alias = "assets", default)]
+    pub sim_asset: Vec<SimulatedAsset>,
+    /// 希腊字母展示方式：BS 美元本位，PA 币本位
+    #[serde(
+        rename = "greeksType",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub greeks_type: Option<String>,
+}
+
+impl PositionBuilderRequest {
+    /// 解析模拟文件内容，按扩展名选择格式：`.yaml`/`.yml` 为YAML，其余为JSON
+    pub fn from_file_content(path: &str, content: &str) -> anyhow::Result<Self> {
+        let path = path.to_ascii_lowercase();
+        if path.ends_with(".yaml") || path.ends_with(".yml") {
+            Ok(serde_yaml::from_str(content)?)
+        } else {
+            Ok(serde_json::from_str(content)?)
+        }
+    }
+
+    /// 把当前持仓加入模拟持仓，返回加入的数量
+    ///
+    /// 加入后不应再设置 `incl_real_pos_and_eq`，否则真实持仓会被重复计算。
+    pub fn seed_from_positions(&mut self, positions: &[Position]) -> usize {
+        let seeded: Vec<SimulatedPosition> = positions
+            .iter()
+            .filter_map(SimulatedPosition::from_position)
+            .filter(|p| !p.pos.is_zero())
+            .collect();
+        let count = seeded.len();
+        self.sim_pos.splice(0..0, seeded);
+        count
+    }
+}
+
+/// 持仓构建器的模拟结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionBuilderResult {
+    /// 美金层面调整后权益
+    #[serde(default, with = "decimal")]
+    pub eq: Option<Decimal>,
+    /// 美金层面占用保证金
+    #[serde(rename = "totalImr", default, with = "decimal")]
+    pub total_imr: Option<Decimal>,
+    /// 美金层面维持保证金
+    #[serde(rename = "totalMmr", default, with = "decimal")]
+    pub total_mmr: Option<Decimal>,
+    /// 借币维持保证金
+    #[serde(rename = "borrowMmr", default, with = "decimal")]
+    pub borrow_mmr: Option<Decimal>,
+    /// 衍生品维持保证金
+    #[serde(rename = "derivMmr", default, with = "decimal")]
+    pub deriv_mmr: Option<Decimal>,
+    /// 保证金率
+    #[serde(rename = "marginRatio", default, with = "decimal")]
+    pub margin_ratio: Option<Decimal>,
+    /// 未实现盈亏
+    #[serde(default, with = "decimal")]
+    pub upl: Option<Decimal>,
+    /// 账户杠杆
+    #[serde(rename = "acctLever", default, with = "decimal")]
+    pub acct_lever: Option<Decimal>,
+    /// 风险单元
+    #[serde(rename = "riskUnitData", default)]
+    pub risk_unit_data: Vec<RiskUnit>,
+    /// 模拟后的持仓
+    #[serde(default)]
+    pub positions: Vec<BuilderPosition>,
+    /// 计算时间
+    #[serde(default, with = "opt_timestamp_ms")]
+    pub ts: Option<DateTime<Utc>>,
+}
+
+/// 风险单元
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskUnit {
+    /// 风险单元，如 `BTC-USDT`
+    #[serde(rename = "riskUnit")]
+    pub risk_unit: String,
+    /// 风险单元的美金价值
+    #[serde(rename = "indexUsd", default, with = "decimal")]
+    pub index_usd: Option<Decimal>,
+    /// 占用保证金
+    #[serde(default, with = "decimal")]
+    pub imr: Option<Decimal>,
+    /// 维持保证金
+    #[serde(default, with = "decimal")]
+    pub mmr: Option<Decimal>,
+    /// 未实现盈亏
+    #[serde(default, with = "decimal")]
+    pub upl: Option<Decimal>,
+    #[serde(default, with = "decimal")]
+    pub delta: Option<Decimal>,
+    #[serde(default, with = "decimal")]
+    pub gamma: Option<Decimal>,
+    #[serde(default, with = "decimal")]
+    pub theta: Option<Decimal>,
+    #[serde(default, with = "decimal")]
+    pub vega: Option<Decimal>,
+}
+
+/// 模拟结果中的持仓
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuilderPosition {
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    #[serde(rename = "instType", default, with = "opt_enum")]
+    pub inst_type: Option<InstType>,
+    #[serde(default, with = "decimal")]
+    pub pos: Option<Decimal>,
+    #[serde(rename = "avgPx", default, with = "decimal")]
+    pub avg_px: Option<Decimal>,
+    #[serde(rename = "notionalUsd", default, with = "decimal")]
+    pub notional_usd: Option<Decimal>,
+    #[serde(default, with = "decimal")]
+    pub imr: Option<Decimal>,
+    #[serde(default, with = "decimal")]
+    pub mmr: Option<Decimal>,
+}
+
+impl PositionBuilderResult {
+    /// 账户层面汇总，一行输出
+    pub fn format_summary(&self) -> String {
+        format!(
+            "权益: {} USD | 占用保证金: {} | 维持保证金: {} | 保证金率: {} | 账户杠杆: {}",
+            format_decimal(&self.eq),
+            format_decimal(&self.total_imr),
+            format_decimal(&self.total_mmr),
+            format_decimal(&self.margin_ratio),
+            format_decimal(&self.acct_lever)
+        )
+    }
+}
+
+impl RiskUnit {
+    /// 表格表头，与 [`RiskUnit::format_display`] 的列对齐
+    pub fn table_header() -> String {
+        format!(
+            "{:<14} | {:<16} | {:<16} | {:<16} | {:<14}",
+            "RiskUnit", "IndexUsd", "Imr", "Mmr", "Delta"
+        )
+    }
+
+    /// 表格格式输出
+    pub fn format_display(&self) -> String {
+        format!(
+            "{:<14} | {:<16} | {:<16} | {:<16} | {:<14}",
+            self.risk_unit,
+            format_decimal(&self.index_usd),
+            format_decimal(&self.imr),
+            format_decimal(&self.mmr),
+            format_decimal(&self.delta)
+        )
+    }
+}
+
+impl OkxClient {
+    /// 持仓构建器：模拟持仓和资产变化后的保证金与风险
+    pub async fn build_positions(
+        &self,
+        request: &PositionBuilderRequest,
+    ) -> Result<ApiResponse<PositionBuilderResult>, OkxError> {
+        self.post("/api/v5/account/position-builder", Some(request))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_from_file_format() {
+        let json = r#"{
+            "acctLv": "4",
+            "positions": [
+                {"instId": "BTC-USDT-SWAP", "pos": "-2", "avgPx": "42000"},
+                {"instId": "BTC-USD-240628-40000-P", "pos": "5", "avgPx": "0.01"}
+            ],
+            "assets": [{"ccy": "USDT", "amt": "1000"}]
+        }"#;
+
+        let request: PositionBuilderRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.acct_lv, Some(AccountLevel::Portfolio));
+        assert_eq!(request.sim_pos.len(), 2);
+        assert_eq!(request.sim_pos[0].pos, Decimal::from(-2));
+
+        let body = serde_json::to_value(&request).unwrap();
+        assert_eq!(body["acctLv"], "4");
+        assert_eq!(body["inclRealPosAndEq"], false);
+        assert_eq!(body["simPos"][0]["avgPx"], "42000");
+        assert_eq!(body["simAsset"][0]["amt"], "1000");
+        assert!(body["simPos"][0].get("lever").is_none());
+        assert!(body.get("greeksType").is_none());
+
+        let example: PositionBuilderRequest =
+            serde_json::from_str(include_str!("../fixtures/simulate_hedge.json")).unwrap();
+        assert_eq!(example.sim_pos.len(), 2);
+        assert_eq!(example.sim_asset[0].ccy, "USDT");
+    }
+
+    #[test]
+    fn test_request_from_yaml_file() {
+        let yaml = PositionBuilderRequest::from_file_content(
+            "fixtures/simulate_hedge.yaml",
+            include_str!("../fixtures/simulate_hedge.yaml"),
+        )
+        .unwrap();
+        let json = PositionBuilderRequest::from_file_content(
+            "fixtures/simulate_hedge.json",
+            include_str!("../fixtures/simulate_hedge.json"),
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(&yaml).unwrap(),
+            serde_json::to_value(&json).unwrap()
+        );
+
+        assert!(PositionBuilderRequest::from_file_content("hedge.json", "positions: []").is_err());
+    }
+
+    #[test]
+    fn test_seed_from_positions() {
+        let position = |pos_side: &str, pos: &str| -> Position {
+            serde_json::from_value(serde_json::json!({
+                "instType": "SWAP", "instId": "ETH-USDT-SWAP", "mgnMode": "cross",
+                "posId": "1", "posSide": pos_side, "pos": pos, "avgPx": "3000",
+                "lever": "5", "cTime": "1619507758793", "uTime": "1619507761462"
+            }))
+            .unwrap()
+        };
+
+        let mut request = PositionBuilderRequest::default();
+        request.sim_pos.push(SimulatedPosition {
+            inst_id: "BTC-USDT-SWAP".to_string(),
+            pos: Decimal::from(1),
+            avg_px: Decimal::from(42000),
+            lever: None,
+        });
+
+        let seeded = request.seed_from_positions(&[
+            position("short", "3"),
+            position("net", "0"),
+            position("long", "2"),
+        ]);
+        assert_eq!(seeded, 2);
+        assert_eq!(request.sim_pos.len(), 3);
+        assert_eq!(request.sim_pos[0].pos, Decimal::from(-3));
+        assert_eq!(request.sim_pos[1].pos, Decimal::from(2));
+        assert_eq!(request.sim_pos[2].inst_id, "BTC-USDT-SWAP");
+    }
+
+    #[test]
+    fn test_result_deserialization() {
+        let json = r#"{
+            "eq": "10000.5", "totalImr": "2200", "totalMmr": "1100", "borrowMmr": "0",
+            "derivMmr": "1100", "marginRatio": "9.09", "upl": "-12", "acctLever": "1.2",
+            "ts": "1705915813386",
+            "riskUnitData": [
+                {"riskUnit": "BTC-USDT", "indexUsd": "84000", "imr": "2200", "mmr": "1100",
+                 "upl": "-12", "delta": "-1", "gamma": "0", "theta": "0", "vega": "0"}
+            ],
+            "positions": [
+                {"instId": "BTC-USDT-SWAP", "instType": "SWAP", "pos": "-2", "avgPx": "42000",
+                 "notionalUsd": "84000", "imr": "", "mmr": ""}
+            ]
+        }"#;
+
+        let result: PositionBuilderResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.total_mmr, Some(Decimal::from(1100)));
+        assert_eq!(result.risk_unit_data[0].risk_unit, "BTC-USDT");
+        assert_eq!(result.positions[0].imr, None);
+        assert!(result.format_summary().contains("保证金率: 9.09"));
+        assert!(result.risk_unit_data[0].format_display().contains("84000"));
+    }
+}
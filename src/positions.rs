@@ -1,62 +1,186 @@
 use crate::{
     client::OkxClient,
     config::Config,
-    types::{ApiResponse, Position, PositionsParams},
+    error::OkxError,
+    types::{decimal, format_decimal, ApiResponse, MarginMode, PosSide, Position, PositionsParams},
 };
 use anyhow::Result;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-/// 获取当前持仓信息
-pub async fn get_positions(
-    config: &Config,
-    params: &PositionsParams,
-) -> Result<ApiResponse<Position>> {
-    let client = OkxClient::new(config.clone());
+impl OkxClient {
+    /// 获取当前持仓信息
+    pub async fn get_positions(
+        &self,
+        params: &PositionsParams,
+    ) -> Result<ApiResponse<Position>, OkxError> {
+        // 构建查询参数
+        let mut query_params = HashMap::new();
 
-    // 构建查询参数
-    let mut query_params = HashMap::new();
-    
-    if let Some(inst_type) = &params.inst_type {
-        query_params.insert("instType".to_string(), inst_type.clone());
+        if let Some(inst_type) = &params.inst_type {
+            query_params.insert("instType".to_string(), inst_type.to_string());
+        }
+
+        if let Some(inst_id) = &params.inst_id {
+            query_params.insert("instId".to_string(), inst_id.clone());
+        }
+
+        if let Some(pos_id) = &params.pos_id {
+            query_params.insert("posId".to_string(), pos_id.clone());
+        }
+
+        // 执行API请求
+        self.get("/api/v5/account/positions", Some(&query_params))
+            .await
     }
-    
-    if let Some(inst_id) = &params.inst_id {
-        query_params.insert("instId".to_string(), inst_id.clone());
+
+    /// 获取简化的持仓信息
+    pub async fn get_positions_simplified(
+        &self,
+        params: &PositionsParams,
+    ) -> Result<Vec<SimplifiedPosition>, OkxError> {
+        let response = self.get_positions(params).await?;
+
+        let simplified_positions: Vec<SimplifiedPosition> = response
+            .data
+            .into_iter()
+            .map(|pos| SimplifiedPosition {
+                avail_pos: format_decimal(&pos.pos),
+                avg_px: format_decimal(&pos.avg_px),
+                mark_px: format_decimal(&pos.mark_px),
+                upl: format_decimal(&pos.upl),
+                upl_ratio: format_decimal(&pos.upl_ratio),
+                pair: pos.inst_id,
+                side: pos.pos_side.to_string(),
+            })
+            .collect();
+
+        Ok(simplified_positions)
     }
-    
-    if let Some(pos_id) = &params.pos_id {
-        query_params.insert("posId".to_string(), pos_id.clone());
+}
+
+/// 逐仓保证金调整方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarginAdjustment {
+    /// 增加保证金
+    Add,
+    /// 减少保证金
+    Reduce,
+}
+
+/// 调整逐仓保证金请求
+#[derive(Debug, Clone, Serialize)]
+pub struct MarginBalanceRequest {
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    /// 持仓方向，买卖模式下为 `net`
+    #[serde(rename = "posSide")]
+    pub pos_side: PosSide,
+    #[serde(rename = "type")]
+    pub adjustment: MarginAdjustment,
+    pub amt: Decimal,
+    /// 增加或减少的保证金币种，仅适用于币币杠杆逐仓
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ccy: Option<String>,
+    /// 是否自动借币，仅适用于币币杠杆逐仓增加保证金
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto: Option<bool>,
+}
+
+/// 调整逐仓保证金的返回
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarginBalanceResult {
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    #[serde(rename = "posSide")]
+    pub pos_side: PosSide,
+    #[serde(rename = "type")]
+    pub adjustment: MarginAdjustment,
+    #[serde(default, with = "decimal")]
+    pub amt: Option<Decimal>,
+    /// 调整后的实际杠杆倍数
+    #[serde(default, with = "decimal")]
+    pub leverage: Option<Decimal>,
+    #[serde(default)]
+    pub ccy: String,
+}
+
+impl OkxClient {
+    /// 增加或减少逐仓保证金
+    pub async fn adjust_margin(
+        &self,
+        request: &MarginBalanceRequest,
+    ) -> Result<ApiResponse<MarginBalanceResult>, OkxError> {
+        if request.amt <= Decimal::ZERO {
+            return Err(OkxError::Request(format!("无效的保证金数量: {}", request.amt)));
+        }
+        self.post("/api/v5/account/position/margin-balance", Some(request))
+            .await
     }
 
-    // 执行API请求
-    let response: ApiResponse<Position> = client
-        .get("/api/v5/account/positions", Some(&query_params))
-        .await?;
+    /// 查询单个产品指定方向的逐仓持仓
+    ///
+    /// 同一产品、同一方向可能同时存在全仓和逐仓持仓，调整保证金只作用于逐仓持仓。
+    pub async fn get_isolated_position(
+        &self,
+        inst_id: &str,
+        pos_side: &PosSide,
+    ) -> Result<Option<Position>, OkxError> {
+        let params = PositionsParams {
+            inst_id: Some(inst_id.to_string()),
+            ..Default::default()
+        };
+        Ok(self
+            .get_positions(&params)
+            .await?
+            .data
+            .into_iter()
+            .find(|p| p.pos_side == *pos_side && p.mgn_mode == MarginMode::Isolated))
+    }
 
-    Ok(response)
+    /// 调整逐仓保证金，并重新查询持仓以获取新的强平价等数据
+    ///
+    /// 调整成功后才会重新查询，查询结果单独返回，查询失败不影响调整结果。
+    pub async fn adjust_margin_and_refresh(
+        &self,
+        request: &MarginBalanceRequest,
+    ) -> Result<(MarginBalanceResult, Result<Option<Position>, OkxError>), OkxError> {
+        let result = self
+            .adjust_margin(request)
+            .await?
+            .data
+            .into_iter()
+            .next()
+            .ok_or_else(|| OkxError::Request("调整保证金未返回结果".to_string()))?;
+        let position = self
+            .get_isolated_position(&request.inst_id, &request.pos_side)
+            .await;
+        Ok((result, position))
+    }
+}
+
+/// 获取当前持仓信息
+///
+/// 兼容旧接口：每次调用都会创建新的客户端，建议使用 [`OkxClient::get_positions`]。
+pub async fn get_positions(
+    config: &Config,
+    params: &PositionsParams,
+) -> Result<ApiResponse<Position>> {
+    Ok(OkxClient::new(config.clone()).get_positions(params).await?)
 }
 
 /// 获取简化的持仓信息
+///
+/// 兼容旧接口：每次调用都会创建新的客户端，建议使用 [`OkxClient::get_positions_simplified`]。
 pub async fn get_positions_simplified(
     config: &Config,
     params: &PositionsParams,
 ) -> Result<Vec<SimplifiedPosition>> {
-    let response = get_positions(config, params).await?;
-    
-    let simplified_positions: Vec<SimplifiedPosition> = response.data
-        .into_iter()
-        .map(|pos| SimplifiedPosition {
-            pair: pos.inst_id,
-            side: pos.pos_side,
-            avail_pos: pos.pos,
-            avg_px: pos.avg_px,
-            mark_px: pos.mark_px,
-            upl: pos.upl,
-            upl_ratio: pos.upl_ratio,
-        })
-        .collect();
-    
-    Ok(simplified_positions)
+    Ok(OkxClient::new(config.clone())
+        .get_positions_simplified(params)
+        .await?)
 }
 
 /// 简化的持仓信息结构
@@ -103,11 +227,12 @@ impl SimplifiedPosition {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::InstType;
 
     #[test]
     fn test_positions_params_serialization() {
         let params = PositionsParams {
-            inst_type: Some("SWAP".to_string()),
+            inst_type: Some(InstType::Swap),
             inst_id: Some("BTC-USD-SWAP".to_string()),
             pos_id: None,
         };
@@ -134,4 +259,35 @@ mod tests {
         assert!(formatted.contains("long"));
         assert!(formatted.contains("1.0"));
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_margin_balance_request_serialization() {
+        let request = MarginBalanceRequest {
+            inst_id: "BTC-USDT-SWAP".to_string(),
+            pos_side: PosSide::Long,
+            adjustment: MarginAdjustment::Add,
+            amt: "12.5".parse().unwrap(),
+            ccy: None,
+            auto: None,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({
+                "instId": "BTC-USDT-SWAP",
+                "posSide": "long",
+                "type": "add",
+                "amt": "12.5"
+            })
+        );
+    }
+
+    #[test]
+    fn test_margin_balance_result_deserialization() {
+        let json = r#"{"amt": "0.3", "ccy": "BTC", "instId": "BTC-USDT", "leverage": "", "posSide": "net", "type": "reduce"}"#;
+        let result: MarginBalanceResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.adjustment, MarginAdjustment::Reduce);
+        assert_eq!(result.pos_side, PosSide::Net);
+        assert_eq!(result.leverage, None);
+    }
+}
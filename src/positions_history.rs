@@ -5,15 +5,17 @@ use std::collections::HashMap;
 use crate::{
     client::OkxClient,
     config::Config,
-    types::{ApiResponse, PositionHistory},
+    error::OkxError,
+    pager::TsCursor,
+    types::{ApiResponse, CloseType, InstType, MarginMode, PositionHistory},
 };
 
 #[derive(Debug, Clone, Serialize)]
 pub struct PositionsHistoryParams {
-    pub inst_type: Option<String>,
+    pub inst_type: Option<InstType>,
     pub inst_id: Option<String>,
-    pub mgn_mode: Option<String>,
-    pub close_type: Option<String>,
+    pub mgn_mode: Option<MarginMode>,
+    pub close_type: Option<CloseType>,
     pub pos_id: Option<String>,
     pub before: Option<String>,
     pub after: Option<String>,
@@ -25,16 +27,16 @@ impl PositionsHistoryParams {
         let mut params = HashMap::new();
         
         if let Some(ref inst_type) = self.inst_type {
-            params.insert("instType".to_string(), inst_type.clone());
+            params.insert("instType".to_string(), inst_type.to_string());
         }
         if let Some(ref inst_id) = self.inst_id {
             params.insert("instId".to_string(), inst_id.clone());
         }
         if let Some(ref mgn_mode) = self.mgn_mode {
-            params.insert("mgnMode".to_string(), mgn_mode.clone());
+            params.insert("mgnMode".to_string(), mgn_mode.to_string());
         }
         if let Some(ref close_type) = self.close_type {
-            params.insert("type".to_string(), close_type.clone());
+            params.insert("type".to_string(), close_type.to_string());
         }
         if let Some(ref pos_id) = self.pos_id {
             params.insert("posId".to_string(), pos_id.clone());
@@ -53,22 +55,104 @@ impl PositionsHistoryParams {
     }
 }
 
+impl OkxClient {
+    /// 获取历史持仓信息
+    pub async fn get_positions_history(
+        &self,
+        params: &PositionsHistoryParams,
+    ) -> Result<ApiResponse<PositionHistory>, OkxError> {
+        // 构建查询参数
+        let query_params = params.to_query_params();
+
+        // 执行API请求
+        self.get("/api/v5/account/positions-history", Some(&query_params))
+            .await
+    }
+}
+
+/// 历史持仓分页查询
+///
+/// 从 `params.after`（为空则从最新记录）开始，按 `uTime` 游标向更早的记录翻页，
+/// 直到没有更多数据或越过 `params.before` 时间下限，游标规则见 [`TsCursor`]。
+/// 每页请求都经过客户端限速器，重复返回的边界记录按 `posId + uTime` 去重。
+pub struct PositionsHistoryPager {
+    client: OkxClient,
+    params: PositionsHistoryParams,
+    cursor: TsCursor<String>,
+}
+
+impl PositionsHistoryPager {
+    pub fn new(client: OkxClient, params: PositionsHistoryParams) -> Self {
+        let cursor = TsCursor::new(
+            params.limit.as_deref(),
+            params.before.as_deref(),
+            params.after.as_deref(),
+        );
+        Self {
+            client,
+            params,
+            cursor,
+        }
+    }
+
+    /// 获取下一页记录，没有更多数据时返回 `None`
+    pub async fn next_page(&mut self) -> Result<Option<Vec<PositionHistory>>, OkxError> {
+        while !self.cursor.is_done() {
+            let mut params = self.params.clone();
+            params.after = self.cursor.cursor().map(|cursor| cursor.to_string());
+            params.limit = Some(self.cursor.limit().to_string());
+
+            let page = self.client.get_positions_history(&params).await?.data;
+            let records = self.cursor.advance(page, history_key);
+            if !records.is_empty() {
+                return Ok(Some(records));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 获取所有剩余记录
+    pub async fn collect_all(mut self) -> Result<Vec<PositionHistory>, OkxError> {
+        let mut all = Vec::new();
+        while let Some(records) = self.next_page().await? {
+            all.extend(records);
+        }
+        Ok(all)
+    }
+}
+
+/// 历史持仓去重键
+fn history_key(position: &PositionHistory) -> (String, i64) {
+    (position.pos_id.clone(), position.u_time.timestamp_millis())
+}
+
+impl OkxClient {
+    /// 创建历史持仓分页查询
+    pub fn positions_history_pager(&self, params: PositionsHistoryParams) -> PositionsHistoryPager {
+        PositionsHistoryPager::new(self.clone(), params)
+    }
+
+    /// 自动翻页获取所有历史持仓
+    pub async fn get_positions_history_all(
+        &self,
+        params: &PositionsHistoryParams,
+    ) -> Result<Vec<PositionHistory>, OkxError> {
+        self.positions_history_pager(params.clone())
+            .collect_all()
+            .await
+    }
+}
+
 /// 获取历史持仓信息
+///
+/// 兼容旧接口：每次调用都会创建新的客户端，建议使用 [`OkxClient::get_positions_history`]。
 pub async fn get_positions_history(
     config: &Config,
     params: &PositionsHistoryParams,
 ) -> Result<ApiResponse<PositionHistory>> {
-    let client = OkxClient::new(config.clone());
-    
-    // 构建查询参数
-    let query_params = params.to_query_params();
-    
-    // 执行API请求
-    let response: ApiResponse<PositionHistory> = client
-        .get("/api/v5/account/positions-history", Some(&query_params))
-        .await?;
-
-    Ok(response)
+    Ok(OkxClient::new(config.clone())
+        .get_positions_history(params)
+        .await?)
 }
 
 #[cfg(test)]
@@ -78,10 +162,10 @@ mod tests {
     #[test]
     fn test_positions_history_params_serialization() {
         let params = PositionsHistoryParams {
-            inst_type: Some("SWAP".to_string()),
+            inst_type: Some(InstType::Swap),
             inst_id: Some("BTC-USD-SWAP".to_string()),
-            mgn_mode: Some("cross".to_string()),
-            close_type: Some("2".to_string()),
+            mgn_mode: Some(MarginMode::Cross),
+            close_type: Some(CloseType::FullyClosed),
             pos_id: None,
             before: None,
             after: None,
@@ -95,4 +179,33 @@ mod tests {
         assert_eq!(query_params.get("type"), Some(&"2".to_string()));
         assert_eq!(query_params.get("limit"), Some(&"50".to_string()));
     }
-} 
\ No newline at end of file
+
+    fn history(pos_id: &str, u_time: i64) -> PositionHistory {
+        let mut value = serde_json::json!({});
+        for field in [
+            "instType", "instId", "mgnMode", "posSide", "openAvgPx", "closeAvgPx",
+            "realizedPnl", "type", "lever", "fee", "fundingFee", "direction",
+            "ccy", "closeTotalPos", "openMaxPos", "pnl", "pnlRatio", "liqPenalty",
+            "nonSettleAvgPx", "settledPnl", "triggerPx", "uly",
+        ] {
+            value[field] = serde_json::json!("");
+        }
+        value["posId"] = serde_json::json!(pos_id);
+        value["cTime"] = serde_json::json!(u_time.to_string());
+        value["uTime"] = serde_json::json!(u_time.to_string());
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_history_key_dedups_by_pos_id() {
+        let mut cursor = TsCursor::new(Some("2"), None, None);
+
+        let page = cursor.advance(vec![history("a", 200), history("b", 100)], history_key);
+        assert_eq!(page.len(), 2);
+
+        // 同一毫秒的 b 被再次返回，c 是新记录
+        let page = cursor.advance(vec![history("b", 100), history("c", 100)], history_key);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].pos_id, "c");
+    }
+}
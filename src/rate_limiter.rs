@@ -1,53 +1,329 @@
 use std::collections::HashMap;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
-use anyhow::Result;
+use tokio::time::delay_for;
 
+/// 限速作用范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitScope {
+    /// 按用户ID限速
+    User,
+    /// 按用户ID + 交易产品限速（下单、撤单、改单等交易接口）
+    Instrument,
+}
+
+/// 单个接口的限速规则：每 `per` 时间内最多 `requests` 次
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EndpointLimit {
+    pub requests: u32,
+    pub per: Duration,
+    pub scope: LimitScope,
+}
+
+impl EndpointLimit {
+    pub const fn new(requests: u32, per: Duration, scope: LimitScope) -> Self {
+        Self {
+            requests,
+            per,
+            scope,
+        }
+    }
+
+    /// 相邻两次请求的平均间隔
+    fn emission_interval(&self) -> Duration {
+        self.per / self.requests.max(1)
+    }
+}
+
+const fn per_2s(requests: u32) -> EndpointLimit {
+    EndpointLimit::new(requests, Duration::from_secs(2), LimitScope::User)
+}
+
+const fn per_instrument_2s(requests: u32) -> EndpointLimit {
+    EndpointLimit::new(requests, Duration::from_secs(2), LimitScope::Instrument)
+}
+
+/// OKX文档中的接口限速
+const ENDPOINT_LIMITS: &[(&str, EndpointLimit)] = &[
+    ("/api/v5/account/balance", per_2s(10)),
+    ("/api/v5/account/positions", per_2s(10)),
+    ("/api/v5/account/positions-history", per_2s(10)),
+    ("/api/v5/account/position/margin-balance", per_2s(20)),
+    ("/api/v5/account/position-builder", per_2s(2)),
+    ("/api/v5/account/config", per_2s(5)),
+    (
+        "/api/v5/account/bills",
+        EndpointLimit::new(5, Duration::from_secs(1), LimitScope::User),
+    ),
+    ("/api/v5/account/bills-archive", per_2s(5)),
+    ("/api/v5/account/leverage-info", per_2s(20)),
+    ("/api/v5/account/set-leverage", per_2s(20)),
+    ("/api/v5/account/set-position-mode", per_2s(5)),
+    ("/api/v5/account/set-account-level", per_2s(5)),
+    ("/api/v5/account/max-size", per_2s(20)),
+    ("/api/v5/account/max-avail-size", per_2s(20)),
+    ("/api/v5/account/max-withdrawal", per_2s(20)),
+    ("/api/v5/account/trade-fee", per_2s(5)),
+    ("/api/v5/account/account-position-risk", per_2s(10)),
+    ("/api/v5/account/greeks", per_2s(10)),
+    ("/api/v5/account/risk-state", per_2s(10)),
+    ("/api/v5/account/interest-accrued", per_2s(5)),
+    ("/api/v5/account/interest-rate", per_2s(5)),
+    ("/api/v5/account/interest-limits", per_2s(5)),
+    (
+        "/api/v5/account/spot-manual-borrow-repay",
+        EndpointLimit::new(1, Duration::from_secs(1), LimitScope::User),
+    ),
+    ("/api/v5/trade/orders-pending", per_2s(60)),
+    ("/api/v5/trade/order", per_instrument_2s(60)),
+    ("/api/v5/trade/cancel-order", per_instrument_2s(60)),
+    ("/api/v5/trade/amend-order", per_instrument_2s(60)),
+    ("/api/v5/trade/batch-orders", per_instrument_2s(300)),
+    ("/api/v5/trade/cancel-batch-orders", per_instrument_2s(300)),
+    ("/api/v5/trade/amend-batch-orders", per_instrument_2s(300)),
+];
+
+/// 记录数超过该值时清理已过期的限速状态
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// 异步限速器（GCRA）
+///
+/// 每个限速键保存一个理论到达时间（TAT），请求在锁内按到达顺序预约时间槽，
+/// 然后在锁外异步等待，因此不会阻塞 Tokio 工作线程，并发任务按先来先得排队。
 pub struct RateLimiter {
-    requests: Mutex<HashMap<String, Vec<Instant>>>,
-    max_requests: usize,
-    window_duration: Duration,
+    limits: HashMap<String, EndpointLimit>,
+    default_limit: EndpointLimit,
+    states: Mutex<HashMap<String, Instant>>,
 }
 
 impl RateLimiter {
-    pub fn new(max_requests: usize, window_duration: Duration) -> Self {
+    /// 使用内置的接口限速表创建限速器
+    pub fn new(default_limit: EndpointLimit) -> Self {
         Self {
-            requests: Mutex::new(HashMap::new()),
-            max_requests,
-            window_duration,
+            limits: ENDPOINT_LIMITS
+                .iter()
+                .map(|(endpoint, limit)| (endpoint.to_string(), *limit))
+                .collect(),
+            default_limit,
+            states: Mutex::new(HashMap::new()),
         }
     }
 
-    pub fn check_rate_limit(&self, user_id: &str) -> Result<bool> {
-        let mut requests = self.requests.lock().unwrap();
+    /// 覆盖或新增某个接口的限速
+    pub fn with_limit(mut self, endpoint: &str, limit: EndpointLimit) -> Self {
+        self.limits.insert(endpoint.to_string(), limit);
+        self
+    }
+
+    /// 接口对应的限速规则，未配置时使用默认规则
+    pub fn limit_for(&self, endpoint: &str) -> EndpointLimit {
+        self.limits
+            .get(endpoint)
+            .copied()
+            .unwrap_or(self.default_limit)
+    }
+
+    /// 等待直到可以发送请求
+    ///
+    /// 按产品限速的接口，`inst_ids` 中的每个产品各占用一次配额（批量接口中同一产品出现多次则计多次）。
+    pub async fn acquire(&self, user_id: &str, endpoint: &str, inst_ids: &[String]) {
+        let delay = self.reserve(user_id, endpoint, inst_ids, Instant::now());
+        if delay > Duration::from_millis(0) {
+            delay_for(delay).await;
+        }
+    }
+
+    /// 不等待，仅检查当前是否可以立即发送请求；可以时占用一次配额
+    pub fn check_rate_limit(&self, user_id: &str, endpoint: &str, inst_ids: &[String]) -> bool {
         let now = Instant::now();
-        
-        // 获取用户请求历史
-        let user_requests = requests.entry(user_id.to_string()).or_insert_with(Vec::new);
-        
-        // 清理过期的请求记录
-        user_requests.retain(|&time| now.duration_since(time) < self.window_duration);
-        
-        // 检查是否超过限速
-        if user_requests.len() >= self.max_requests {
-            return Ok(false);
+        let limit = self.limit_for(endpoint);
+        let keys = self.keys(user_id, endpoint, inst_ids, limit);
+
+        let mut states = self.states.lock().unwrap();
+        let allowed = keys.iter().all(|(key, weight)| {
+            gcra_delay(states.get(key).copied(), limit, *weight, now).1 == Duration::from_millis(0)
+        });
+        if allowed {
+            for (key, weight) in keys {
+                let (tat, _) = gcra_delay(states.get(&key).copied(), limit, weight, now);
+                states.insert(key, tat);
+            }
         }
-        
-        // 添加当前请求
-        user_requests.push(now);
-        Ok(true)
+        allowed
     }
 
-    pub fn wait_if_needed(&self, user_id: &str) -> Result<()> {
-        while !self.check_rate_limit(user_id)? {
-            std::thread::sleep(Duration::from_millis(100));
+    /// 预约时间槽，返回需要等待的时间
+    fn reserve(
+        &self,
+        user_id: &str,
+        endpoint: &str,
+        inst_ids: &[String],
+        now: Instant,
+    ) -> Duration {
+        let limit = self.limit_for(endpoint);
+        let keys = self.keys(user_id, endpoint, inst_ids, limit);
+
+        let mut states = self.states.lock().unwrap();
+        if states.len() > PRUNE_THRESHOLD {
+            states.retain(|_, tat| *tat > now);
         }
-        Ok(())
+
+        let mut delay = Duration::from_millis(0);
+        for (key, weight) in keys {
+            let (tat, key_delay) = gcra_delay(states.get(&key).copied(), limit, weight, now);
+            states.insert(key, tat);
+            delay = delay.max(key_delay);
+        }
+        delay
+    }
+
+    /// 计算限速键及每个键占用的配额
+    fn keys(
+        &self,
+        user_id: &str,
+        endpoint: &str,
+        inst_ids: &[String],
+        limit: EndpointLimit,
+    ) -> Vec<(String, u32)> {
+        if limit.scope == LimitScope::User || inst_ids.is_empty() {
+            return vec![(format!("{}:{}", user_id, endpoint), 1)];
+        }
+
+        let mut weights: Vec<(String, u32)> = Vec::new();
+        for inst_id in inst_ids {
+            let key = format!("{}:{}:{}", user_id, endpoint, inst_id);
+            match weights.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, weight)) => *weight += 1,
+                None => weights.push((key, 1)),
+            }
+        }
+        weights
     }
 }
 
+/// GCRA：根据上一次的理论到达时间计算新的TAT和需要等待的时间
+fn gcra_delay(
+    tat: Option<Instant>,
+    limit: EndpointLimit,
+    weight: u32,
+    now: Instant,
+) -> (Instant, Duration) {
+    let tat = tat.map_or(now, |tat| tat.max(now));
+    let new_tat = tat + limit.emission_interval() * weight;
+    let allow_at = new_tat.checked_sub(limit.per).unwrap_or(now);
+
+    (new_tat, allow_at.saturating_duration_since(now))
+}
+
 impl Default for RateLimiter {
     fn default() -> Self {
-        Self::new(10, Duration::from_secs(2))
+        Self::new(per_2s(10))
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_burst_then_spacing() {
+        let limiter = RateLimiter::default();
+        let now = Instant::now();
+
+        // 10次/2秒：前10次立即放行，之后每200ms一次
+        for _ in 0..10 {
+            assert_eq!(
+                limiter.reserve("user", "/api/v5/account/positions", &[], now),
+                ms(0)
+            );
+        }
+        assert_eq!(
+            limiter.reserve("user", "/api/v5/account/positions", &[], now),
+            ms(200)
+        );
+        assert_eq!(
+            limiter.reserve("user", "/api/v5/account/positions", &[], now),
+            ms(400)
+        );
+    }
+
+    #[test]
+    fn test_endpoints_have_separate_buckets() {
+        let limiter = RateLimiter::default();
+        let now = Instant::now();
+
+        for _ in 0..5 {
+            limiter.reserve("user", "/api/v5/account/config", &[], now);
+        }
+        assert!(limiter.reserve("user", "/api/v5/account/config", &[], now) > ms(0));
+        assert_eq!(
+            limiter.reserve("user", "/api/v5/account/balance", &[], now),
+            ms(0)
+        );
+        assert_eq!(
+            limiter.reserve("other", "/api/v5/account/config", &[], now),
+            ms(0)
+        );
+    }
+
+    #[test]
+    fn test_instrument_scope() {
+        let limiter = RateLimiter::default();
+        let now = Instant::now();
+        let btc = vec!["BTC-USDT".to_string()];
+        let eth = vec!["ETH-USDT".to_string()];
+
+        for _ in 0..60 {
+            assert_eq!(
+                limiter.reserve("user", "/api/v5/trade/order", &btc, now),
+                ms(0)
+            );
+        }
+        assert!(limiter.reserve("user", "/api/v5/trade/order", &btc, now) > ms(0));
+        assert_eq!(
+            limiter.reserve("user", "/api/v5/trade/order", &eth, now),
+            ms(0)
+        );
+    }
+
+    #[test]
+    fn test_batch_counts_each_order() {
+        let limiter =
+            RateLimiter::default().with_limit("/api/v5/trade/batch-orders", per_instrument_2s(4));
+        let now = Instant::now();
+        let orders: Vec<String> = vec!["BTC-USDT".to_string(); 4];
+
+        assert_eq!(
+            limiter.reserve("user", "/api/v5/trade/batch-orders", &orders, now),
+            ms(0)
+        );
+        assert_eq!(
+            limiter.reserve("user", "/api/v5/trade/batch-orders", &orders[..1], now),
+            ms(500)
+        );
+    }
+
+    #[test]
+    fn test_check_rate_limit_does_not_reserve_when_limited() {
+        let limiter = RateLimiter::default().with_limit("/test", per_2s(1));
+
+        assert!(limiter.check_rate_limit("user", "/test", &[]));
+        assert!(!limiter.check_rate_limit("user", "/test", &[]));
+        assert!(!limiter.check_rate_limit("user", "/test", &[]));
+    }
+
+    #[test]
+    fn test_fifo_reservations() {
+        let limiter = RateLimiter::default().with_limit("/test", per_2s(2));
+        let now = Instant::now();
+
+        let delays: Vec<Duration> = (0..5)
+            .map(|_| limiter.reserve("user", "/test", &[], now))
+            .collect();
+        assert_eq!(delays, vec![ms(0), ms(0), ms(1000), ms(2000), ms(3000)]);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::error::OkxError;
+use rand::Rng;
+use std::time::Duration;
+
+/// 请求重试策略
+///
+/// 失败后按指数退避等待：`base_delay * 2^(attempt-1)`，不超过 `max_delay`，
+/// 开启 `jitter` 时在 [0, delay] 范围内随机取值（full jitter）。
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// 最大尝试次数（包括第一次请求），1 表示不重试
+    pub max_attempts: u32,
+    /// 初始退避时间
+    pub base_delay: Duration,
+    /// 最大退避时间
+    pub max_delay: Duration,
+    /// 是否对退避时间加随机抖动
+    pub jitter: bool,
+    /// 是否重试限速错误（HTTP 429 / 50011）
+    pub retry_rate_limited: bool,
+    /// 是否重试服务端错误（5xx / 系统繁忙类错误码）
+    pub retry_server_errors: bool,
+    /// 是否重试网络错误（超时 / 连接失败）
+    pub retry_network_errors: bool,
+}
+
+impl RetryPolicy {
+    /// 不重试
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// 第 `attempt` 次失败后的退避时间（attempt 从1开始）
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exp = attempt.saturating_sub(1).min(16);
+        let delay = self
+            .base_delay
+            .checked_mul(1 << exp)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+
+        if self.jitter && delay > Duration::from_millis(0) {
+            let millis = delay.as_millis() as u64;
+            Duration::from_millis(rand::thread_rng().gen_range(0..=millis))
+        } else {
+            delay
+        }
+    }
+
+    /// 第 `attempt` 次请求失败后是否应当重试
+    ///
+    /// 非幂等请求（如下单）只在确定服务端未处理时重试：限速拒绝或连接未建立。
+    /// 超时和5xx无法确定订单是否已被受理，因此不会重试。
+    pub fn should_retry(&self, err: &OkxError, idempotent: bool, attempt: u32) -> bool {
+        if attempt >= self.max_attempts || !err.is_retryable() {
+            return false;
+        }
+
+        if err.is_rate_limited() {
+            return self.retry_rate_limited;
+        }
+
+        match err {
+            OkxError::Network { source, .. } => {
+                self.retry_network_errors && (idempotent || source.is_connect())
+            }
+            _ => self.retry_server_errors && idempotent,
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+            retry_rate_limited: true,
+            retry_server_errors: true,
+            retry_network_errors: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_jitter() -> RetryPolicy {
+        RetryPolicy {
+            jitter: false,
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_backoff_exponential_and_capped() {
+        let policy = no_jitter();
+        assert_eq!(policy.backoff(1), Duration::from_millis(500));
+        assert_eq!(policy.backoff(2), Duration::from_millis(1000));
+        assert_eq!(policy.backoff(3), Duration::from_millis(2000));
+        assert_eq!(policy.backoff(10), Duration::from_secs(10));
+        assert_eq!(policy.backoff(100), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_backoff_jitter_within_bounds() {
+        let policy = RetryPolicy::default();
+        for attempt in 1..6 {
+            assert!(policy.backoff(attempt) <= no_jitter().backoff(attempt));
+        }
+    }
+
+    #[test]
+    fn test_should_retry_server_error_only_when_idempotent() {
+        let policy = no_jitter();
+        let err = OkxError::http("/api/v5/account/positions-history", 502, "Bad Gateway");
+
+        assert!(policy.should_retry(&err, true, 1));
+        assert!(policy.should_retry(&err, true, 2));
+        assert!(!policy.should_retry(&err, true, 3));
+        assert!(!policy.should_retry(&err, false, 1));
+    }
+
+    #[test]
+    fn test_should_retry_rate_limited_even_when_not_idempotent() {
+        let policy = no_jitter();
+        let err = OkxError::http(
+            "/api/v5/trade/order",
+            429,
+            r#"{"code":"50011","msg":"Too Many Requests"}"#,
+        );
+
+        assert!(policy.should_retry(&err, false, 1));
+
+        let policy = RetryPolicy {
+            retry_rate_limited: false,
+            ..no_jitter()
+        };
+        assert!(!policy.should_retry(&err, false, 1));
+    }
+
+    #[test]
+    fn test_should_not_retry_permanent_errors() {
+        let err = OkxError::http(
+            "/api/v5/account/balance",
+            401,
+            r#"{"code":"50113","msg":"Invalid Sign"}"#,
+        );
+        assert!(!no_jitter().should_retry(&err, true, 1));
+        assert!(!RetryPolicy::none().should_retry(
+            &OkxError::http("/api/v5/account/balance", 503, ""),
+            true,
+            1
+        ));
+    }
+}
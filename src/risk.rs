@@ -0,0 +1,234 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{
+    client::OkxClient,
+    error::OkxError,
+    time::{opt_timestamp_ms, timestamp_ms},
+    types::{decimal, format_decimal, opt_enum, ApiResponse, InstType, MarginMode, PosSide},
+};
+
+/// 账户和持仓风险，对应 `/api/v5/account/account-position-risk`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountPositionRisk {
+    /// 美金层面有效保证金
+    #[serde(rename = "adjEq", default, with = "decimal")]
+    pub adj_eq: Option<Decimal>,
+    /// 币种资产
+    #[serde(rename = "balData", default)]
+    pub bal_data: Vec<RiskBalance>,
+    /// 持仓
+    #[serde(rename = "posData", default)]
+    pub pos_data: Vec<RiskPosition>,
+    /// 数据更新时间
+    #[serde(with = "timestamp_ms")]
+    pub ts: DateTime<Utc>,
+}
+
+/// 风险数据中的币种资产
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskBalance {
+    /// 币种
+    pub ccy: String,
+    /// 币种总权益
+    #[serde(default, with = "decimal")]
+    pub eq: Option<Decimal>,
+    /// 美金层面币种折算权益
+    #[serde(rename = "disEq", default, with = "decimal")]
+    pub dis_eq: Option<Decimal>,
+}
+
+/// 风险数据中的持仓
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskPosition {
+    /// 产品类型
+    #[serde(rename = "instType")]
+    pub inst_type: InstType,
+    /// 产品ID
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    /// 保证金模式
+    #[serde(rename = "mgnMode")]
+    pub mgn_mode: MarginMode,
+    /// 持仓ID
+    #[serde(rename = "posId", default)]
+    pub pos_id: String,
+    /// 持仓方向
+    #[serde(rename = "posSide", default, with = "opt_enum")]
+    pub pos_side: Option<PosSide>,
+    /// 持仓数量
+    #[serde(default, with = "decimal")]
+    pub pos: Option<Decimal>,
+    /// 占用保证金的币种
+    #[serde(default)]
+    pub ccy: String,
+    /// 以币为单位的持仓价值
+    #[serde(rename = "notionalCcy", default, with = "decimal")]
+    pub notional_ccy: Option<Decimal>,
+    /// 以美金为单位的持仓价值
+    #[serde(rename = "notionalUsd", default, with = "decimal")]
+    pub notional_usd: Option<Decimal>,
+}
+
+/// 账户希腊字母，PA 为币本位，BS 为美元本位
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Greeks {
+    /// 币种
+    pub ccy: String,
+    #[serde(rename = "deltaBS", default, with = "decimal")]
+    pub delta_bs: Option<Decimal>,
+    #[serde(rename = "deltaPA", default, with = "decimal")]
+    pub delta_pa: Option<Decimal>,
+    #[serde(rename = "gammaBS", default, with = "decimal")]
+    pub gamma_bs: Option<Decimal>,
+    #[serde(rename = "gammaPA", default, with = "decimal")]
+    pub gamma_pa: Option<Decimal>,
+    #[serde(rename = "thetaBS", default, with = "decimal")]
+    pub theta_bs: Option<Decimal>,
+    #[serde(rename = "thetaPA", default, with = "decimal")]
+    pub theta_pa: Option<Decimal>,
+    #[serde(rename = "vegaBS", default, with = "decimal")]
+    pub vega_bs: Option<Decimal>,
+    #[serde(rename = "vegaPA", default, with = "decimal")]
+    pub vega_pa: Option<Decimal>,
+    /// 数据更新时间
+    #[serde(default, with = "opt_timestamp_ms")]
+    pub ts: Option<DateTime<Utc>>,
+}
+
+impl Greeks {
+    /// 表格表头，与 [`Greeks::format_display`] 的列对齐
+    pub fn table_header() -> String {
+        format!(
+            "{:<8} | {:<18} | {:<18} | {:<18} | {:<18}",
+            "Ccy", "Delta", "Gamma", "Vega", "Theta"
+        )
+    }
+
+    /// 表格格式输出，`pa` 为 true 时使用币本位（PA）数值，否则使用美元本位（BS）
+    pub fn format_display(&self, pa: bool) -> String {
+        let (delta, gamma, vega, theta) = if pa {
+            (self.delta_pa, self.gamma_pa, self.vega_pa, self.theta_pa)
+        } else {
+            (self.delta_bs, self.gamma_bs, self.vega_bs, self.theta_bs)
+        };
+        format!(
+            "{:<8} | {:<18} | {:<18} | {:<18} | {:<18}",
+            self.ccy,
+            format_decimal(&delta),
+            format_decimal(&gamma),
+            format_decimal(&vega),
+            format_decimal(&theta)
+        )
+    }
+}
+
+/// 组合保证金账户的风险状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskState {
+    /// 是否处于自动借币风险
+    #[serde(rename = "atRisk", default)]
+    pub at_risk: bool,
+    /// 处于风险中的衍生品风险单元
+    #[serde(rename = "atRiskIdx", default)]
+    pub at_risk_idx: Vec<String>,
+    /// 处于风险中的币币杠杆风险单元
+    #[serde(rename = "atRiskMgn", default)]
+    pub at_risk_mgn: Vec<String>,
+    /// 数据更新时间
+    #[serde(default, with = "opt_timestamp_ms")]
+    pub ts: Option<DateTime<Utc>>,
+}
+
+impl OkxClient {
+    /// 获取账户和持仓风险，`inst_type` 为空时返回所有产品类型
+    pub async fn get_account_position_risk(
+        &self,
+        inst_type: Option<&InstType>,
+    ) -> Result<ApiResponse<AccountPositionRisk>, OkxError> {
+        let mut query_params = HashMap::new();
+        if let Some(inst_type) = inst_type {
+            query_params.insert("instType".to_string(), inst_type.to_string());
+        }
+        self.get("/api/v5/account/account-position-risk", Some(&query_params))
+            .await
+    }
+
+    /// 获取账户希腊字母，`ccy` 为空时返回所有币种
+    pub async fn get_greeks(&self, ccy: Option<&str>) -> Result<ApiResponse<Greeks>, OkxError> {
+        let mut query_params = HashMap::new();
+        if let Some(ccy) = ccy {
+            query_params.insert("ccy".to_string(), ccy.to_string());
+        }
+        self.get("/api/v5/account/greeks", Some(&query_params))
+            .await
+    }
+
+    /// 获取组合保证金账户的风险状态
+    pub async fn get_risk_state(&self) -> Result<ApiResponse<RiskState>, OkxError> {
+        self.get("/api/v5/account/risk-state", None).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_position_risk_deserialization() {
+        let json = r#"{
+            "adjEq": "174238.6793649711331679",
+            "balData": [
+                {"ccy": "BTC", "disEq": "78846.7803721021362242", "eq": "1.3863533369419636"},
+                {"ccy": "USDT", "disEq": "73417.2495112863300127", "eq": "73323.395564963177146"}
+            ],
+            "posData": [
+                {
+                    "baseBal": "0.4", "ccy": "", "instId": "BTC-USDT", "instType": "MARGIN",
+                    "mgnMode": "isolated", "notionalCcy": "0", "notionalUsd": "0",
+                    "pos": "0", "posCcy": "", "posId": "310388685292318723",
+                    "posSide": "net", "quoteBal": "0"
+                }
+            ],
+            "ts": "1620282889345"
+        }"#;
+
+        let risk: AccountPositionRisk = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            risk.adj_eq,
+            Some("174238.6793649711331679".parse().unwrap())
+        );
+        assert_eq!(risk.bal_data[1].ccy, "USDT");
+        assert_eq!(risk.pos_data[0].inst_type, InstType::Margin);
+        assert_eq!(risk.pos_data[0].pos_side, Some(PosSide::Net));
+        assert_eq!(risk.ts.timestamp_millis(), 1620282889345);
+    }
+
+    #[test]
+    fn test_greeks_table_row() {
+        let json = r#"{
+            "ccy": "BTC", "deltaBS": "1.1246665401", "deltaPA": "-0.0000042",
+            "gammaBS": "0.0000000123", "gammaPA": "0.00000034", "thetaBS": "-4.3",
+            "thetaPA": "-0.0001", "vegaBS": "23.5", "vegaPA": "0.0005", "ts": "1620282889345"
+        }"#;
+
+        let greeks: Greeks = serde_json::from_str(json).unwrap();
+        let pa = greeks.format_display(true);
+        assert!(pa.contains("-0.0000042"));
+        assert!(pa.contains("-0.0001"));
+
+        let bs = greeks.format_display(false);
+        assert!(bs.contains("1.1246665401"));
+        assert!(bs.contains("23.5"));
+    }
+
+    #[test]
+    fn test_risk_state_deserialization() {
+        let json = r#"{"atRisk": false, "atRiskIdx": [], "atRiskMgn": [], "ts": "1635745078794"}"#;
+        let state: RiskState = serde_json::from_str(json).unwrap();
+        assert!(!state.at_risk);
+        assert!(state.at_risk_idx.is_empty());
+    }
+}
@@ -0,0 +1,333 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde_json::Value;
+use std::str::FromStr;
+
+/// OKX 响应中的毫秒时间戳字段名，用于按时区格式化输出
+pub const TIME_FIELDS: &[&str] = &[
+    "cTime",
+    "uTime",
+    "ts",
+    "fundingTime",
+    "lastPxTime",
+    "optValTime",
+    "optValLastPxTime",
+    "optValFundingTime",
+];
+
+/// 输出时使用的时区
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisplayTz {
+    Utc,
+    /// 系统本地时区
+    Local,
+    /// 固定偏移，如 `+08:00`
+    Fixed(FixedOffset),
+    /// IANA 时区，如 `Asia/Shanghai`
+    Named(Tz),
+}
+
+impl DisplayTz {
+    /// 格式化为该时区的 `YYYY-MM-DD HH:MM:SS.mmm +08:00`
+    pub fn format(&self, time: &DateTime<Utc>) -> String {
+        const FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f %:z";
+        match self {
+            DisplayTz::Utc => time.format(FORMAT).to_string(),
+            DisplayTz::Local => time.with_timezone(&Local).format(FORMAT).to_string(),
+            DisplayTz::Fixed(offset) => time.with_timezone(offset).format(FORMAT).to_string(),
+            DisplayTz::Named(tz) => time.with_timezone(tz).format(FORMAT).to_string(),
+        }
+    }
+
+    /// 该时区下的日期
+    pub fn date(&self, time: &DateTime<Utc>) -> NaiveDate {
+        match self {
+            DisplayTz::Utc => time.date_naive(),
+            DisplayTz::Local => time.with_timezone(&Local).date_naive(),
+            DisplayTz::Fixed(offset) => time.with_timezone(offset).date_naive(),
+            DisplayTz::Named(tz) => time.with_timezone(tz).date_naive(),
+        }
+    }
+
+    /// 把该时区的本地时间转换为UTC
+    fn to_utc(self, naive: NaiveDateTime) -> Result<DateTime<Utc>> {
+        let time = match self {
+            DisplayTz::Utc => Utc.from_local_datetime(&naive).single(),
+            DisplayTz::Local => Local
+                .from_local_datetime(&naive)
+                .single()
+                .map(|t| t.with_timezone(&Utc)),
+            DisplayTz::Fixed(offset) => offset
+                .from_local_datetime(&naive)
+                .single()
+                .map(|t| t.with_timezone(&Utc)),
+            DisplayTz::Named(tz) => tz
+                .from_local_datetime(&naive)
+                .single()
+                .map(|t| t.with_timezone(&Utc)),
+        };
+        time.ok_or_else(|| anyhow!("时间 {} 在该时区不存在或有歧义", naive))
+    }
+}
+
+impl FromStr for DisplayTz {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("utc") || s.eq_ignore_ascii_case("z") {
+            return Ok(DisplayTz::Utc);
+        }
+        if s.eq_ignore_ascii_case("local") {
+            return Ok(DisplayTz::Local);
+        }
+        if let Some(offset) = parse_offset(s) {
+            return Ok(DisplayTz::Fixed(offset));
+        }
+        s.parse::<Tz>().map(DisplayTz::Named).map_err(|_| {
+            anyhow!(
+                "无效的时区: {} (可用 UTC、local、+08:00 或 IANA 名称如 Asia/Shanghai)",
+                s
+            )
+        })
+    }
+}
+
+/// 解析 `+08:00` / `-0530` 形式的偏移
+fn parse_offset(s: &str) -> Option<FixedOffset> {
+    let (sign, rest) = match s.as_bytes().first()? {
+        b'+' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    let digits: String = rest.chars().filter(|c| *c != ':').collect();
+    if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i32 = digits[..2].parse().ok()?;
+    let minutes: i32 = digits[2..].parse().ok()?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// 解析相对时长，如 `30m`、`12h`、`7d`、`2w`
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let unit = s.chars().last()?;
+    let amount: i64 = s[..s.len() - unit.len_utf8()].parse().ok()?;
+    match unit {
+        's' => Some(Duration::seconds(amount)),
+        'm' => Some(Duration::minutes(amount)),
+        'h' => Some(Duration::hours(amount)),
+        'd' => Some(Duration::days(amount)),
+        'w' => Some(Duration::weeks(amount)),
+        _ => None,
+    }
+}
+
+/// 解析命令行中的时间参数
+///
+/// 支持毫秒时间戳、RFC 3339（`2024-01-01T08:00:00Z`）、`YYYY-MM-DD[ HH:MM[:SS]]`
+/// （按 `tz` 时区解释）以及相对 `now` 的时长（`7d` 表示7天前）。
+pub fn parse_time(s: &str, now: DateTime<Utc>, tz: DisplayTz) -> Result<DateTime<Utc>> {
+    let s = s.trim();
+
+    if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) {
+        let millis: i64 = s.parse()?;
+        return Utc
+            .timestamp_millis_opt(millis)
+            .single()
+            .ok_or_else(|| anyhow!("无效的毫秒时间戳: {}", s));
+    }
+    if let Some(duration) = parse_duration(s) {
+        return Ok(now - duration);
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(s) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(s, format) {
+            return tz.to_utc(naive);
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return tz.to_utc(date.and_hms_opt(0, 0, 0).unwrap());
+    }
+
+    Err(anyhow!(
+        "无法解析时间: {} (支持毫秒时间戳、2024-01-01、2024-01-01T08:00:00Z 或 7d/12h 等相对时间)",
+        s
+    ))
+}
+
+/// 把JSON中的毫秒时间戳字段（见 [`TIME_FIELDS`]）替换为指定时区的可读时间
+pub fn localize_times(value: &mut Value, tz: DisplayTz) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                let time = if TIME_FIELDS.contains(&key.as_str()) {
+                    field.as_str().and_then(timestamp_ms::parse)
+                } else {
+                    None
+                };
+                match time {
+                    Some(time) => *field = Value::String(tz.format(&time)),
+                    None => localize_times(field, tz),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                localize_times(item, tz);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// OKX毫秒时间戳字段的序列化：字符串形式的Unix毫秒数
+pub mod timestamp_ms {
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&value.timestamp_millis().to_string())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        parse(&s).ok_or_else(|| de::Error::custom(format!("无效的毫秒时间戳: {}", s)))
+    }
+
+    pub(crate) fn parse(s: &str) -> Option<DateTime<Utc>> {
+        let millis: i64 = s.trim().parse().ok()?;
+        Utc.timestamp_millis_opt(millis).single()
+    }
+}
+
+/// 可选毫秒时间戳字段，空字符串或 `null` 对应 `None`
+pub mod opt_timestamp_ms {
+    use chrono::{DateTime, Utc};
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(value) => super::timestamp_ms::serialize(value, serializer),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(s) if !s.trim().is_empty() => super::timestamp_ms::parse(&s)
+                .map(Some)
+                .ok_or_else(|| de::Error::custom(format!("无效的毫秒时间戳: {}", s))),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 10, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_time_formats() {
+        let utc = DisplayTz::Utc;
+        assert_eq!(parse_time("1710072000000", now(), utc).unwrap(), now());
+        assert_eq!(
+            parse_time("2024-03-10T20:00:00+08:00", now(), utc).unwrap(),
+            now()
+        );
+        assert_eq!(
+            parse_time("2024-03-10 12:00:00", now(), utc).unwrap(),
+            now()
+        );
+        assert_eq!(
+            parse_time("2024-03-10", now(), utc).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_time_uses_tz_for_naive_dates() {
+        let shanghai: DisplayTz = "Asia/Shanghai".parse().unwrap();
+        assert_eq!(
+            parse_time("2024-03-10", now(), shanghai).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 9, 16, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_relative_time() {
+        let utc = DisplayTz::Utc;
+        assert_eq!(
+            parse_time("7d", now(), utc).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 3, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_time("12h", now(), utc).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap()
+        );
+        assert!(parse_time("7x", now(), utc).is_err());
+    }
+
+    #[test]
+    fn test_display_tz() {
+        let tz: DisplayTz = "+08:00".parse().unwrap();
+        assert_eq!(tz.format(&now()), "2024-03-10 20:00:00.000 +08:00");
+
+        let tz: DisplayTz = "America/New_York".parse().unwrap();
+        assert_eq!(tz.format(&now()), "2024-03-10 08:00:00.000 -04:00");
+
+        assert!("Mars/Olympus".parse::<DisplayTz>().is_err());
+    }
+
+    #[test]
+    fn test_localize_times() {
+        let mut value = serde_json::json!({
+            "data": [{"instId": "BTC-USDT", "uTime": "1710072000000", "fundingTime": ""}]
+        });
+        localize_times(&mut value, "+08:00".parse().unwrap());
+
+        assert_eq!(value["data"][0]["uTime"], "2024-03-10 20:00:00.000 +08:00");
+        assert_eq!(value["data"][0]["fundingTime"], "");
+        assert_eq!(value["data"][0]["instId"], "BTC-USDT");
+    }
+
+    #[test]
+    fn test_timestamp_serde() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Record {
+            #[serde(with = "timestamp_ms")]
+            c_time: DateTime<Utc>,
+            #[serde(default, with = "opt_timestamp_ms")]
+            funding_time: Option<DateTime<Utc>>,
+        }
+
+        let record: Record =
+            serde_json::from_str(r#"{"c_time":"1710072000000","funding_time":""}"#).unwrap();
+        assert_eq!(record.c_time, now());
+        assert_eq!(record.funding_time, None);
+
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["c_time"], "1710072000000");
+        assert_eq!(json["funding_time"], "");
+    }
+}
@@ -0,0 +1,900 @@
+use crate::{
+    client::OkxClient,
+    error::OkxError,
+    time::opt_timestamp_ms,
+    types::{ApiResponse, OrdType, PosSide, Side, TradeMode},
+};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+/// 下单时附带的止盈止损
+///
+/// 委托价为 `-1`（[`AttachAlgoOrder::MARKET_PX`]）时，触发后以市价执行。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AttachAlgoOrder {
+    /// 客户自定义的策略订单ID
+    #[serde(rename = "attachAlgoClOrdId", skip_serializing_if = "Option::is_none")]
+    pub attach_algo_cl_ord_id: Option<String>,
+    #[serde(rename = "tpTriggerPx", skip_serializing_if = "Option::is_none")]
+    pub tp_trigger_px: Option<Decimal>,
+    #[serde(rename = "tpOrdPx", skip_serializing_if = "Option::is_none")]
+    pub tp_ord_px: Option<Decimal>,
+    #[serde(rename = "slTriggerPx", skip_serializing_if = "Option::is_none")]
+    pub sl_trigger_px: Option<Decimal>,
+    #[serde(rename = "slOrdPx", skip_serializing_if = "Option::is_none")]
+    pub sl_ord_px: Option<Decimal>,
+}
+
+impl AttachAlgoOrder {
+    /// 市价执行的委托价
+    pub const MARKET_PX: Decimal = Decimal::NEGATIVE_ONE;
+
+    /// 止盈，委托价为 `None` 时按市价执行
+    pub fn take_profit(trigger_px: Decimal, ord_px: Option<Decimal>) -> Self {
+        Self::default().with_take_profit(trigger_px, ord_px)
+    }
+
+    /// 止损，委托价为 `None` 时按市价执行
+    pub fn stop_loss(trigger_px: Decimal, ord_px: Option<Decimal>) -> Self {
+        Self::default().with_stop_loss(trigger_px, ord_px)
+    }
+
+    pub fn with_take_profit(mut self, trigger_px: Decimal, ord_px: Option<Decimal>) -> Self {
+        self.tp_trigger_px = Some(trigger_px);
+        self.tp_ord_px = Some(ord_px.unwrap_or(Self::MARKET_PX));
+        self
+    }
+
+    pub fn with_stop_loss(mut self, trigger_px: Decimal, ord_px: Option<Decimal>) -> Self {
+        self.sl_trigger_px = Some(trigger_px);
+        self.sl_ord_px = Some(ord_px.unwrap_or(Self::MARKET_PX));
+        self
+    }
+
+    fn validate(&self) -> Result<(), OkxError> {
+        if self.tp_trigger_px.is_none() && self.sl_trigger_px.is_none() {
+            return Err(OkxError::Request(
+                "止盈止损至少需要指定一个触发价".to_string(),
+            ));
+        }
+        for (label, trigger_px, ord_px) in [
+            ("止盈", self.tp_trigger_px, self.tp_ord_px),
+            ("止损", self.sl_trigger_px, self.sl_ord_px),
+        ] {
+            if trigger_px.is_some() != ord_px.is_some() {
+                return Err(OkxError::Request(format!(
+                    "{}需要同时指定触发价和委托价（市价为 -1）",
+                    label
+                )));
+            }
+            if let Some(px) = trigger_px.filter(|px| *px <= Decimal::ZERO) {
+                return Err(OkxError::Request(format!("无效的{}触发价: {}", label, px)));
+            }
+        }
+        if let Some(id) = &self.attach_algo_cl_ord_id {
+            validate_id("attachAlgoClOrdId", id, 32)?;
+        }
+        Ok(())
+    }
+}
+
+/// 下单请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaceOrderRequest {
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    #[serde(rename = "tdMode")]
+    pub td_mode: TradeMode,
+    pub side: Side,
+    /// 持仓方向，开平仓模式下交割/永续必填
+    #[serde(rename = "posSide", skip_serializing_if = "Option::is_none")]
+    pub pos_side: Option<PosSide>,
+    #[serde(rename = "ordType")]
+    pub ord_type: OrdType,
+    pub sz: Decimal,
+    /// 委托价格，仅适用于 limit、post_only、fok、ioc
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub px: Option<Decimal>,
+    /// 是否只减仓
+    #[serde(rename = "reduceOnly", skip_serializing_if = "Option::is_none")]
+    pub reduce_only: Option<bool>,
+    /// 客户自定义订单ID，1-32位字母数字
+    #[serde(rename = "clOrdId", skip_serializing_if = "Option::is_none")]
+    pub cl_ord_id: Option<String>,
+    /// 订单标签，1-16位字母数字
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// 保证金币种，仅适用于跨币种保证金模式下的全仓币币杠杆
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ccy: Option<String>,
+    #[serde(
+        rename = "attachAlgoOrds",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub attach_algo_ords: Vec<AttachAlgoOrder>,
+}
+
+impl PlaceOrderRequest {
+    pub fn new(
+        inst_id: impl Into<String>,
+        td_mode: TradeMode,
+        side: Side,
+        ord_type: OrdType,
+        sz: Decimal,
+    ) -> Self {
+        Self {
+            inst_id: inst_id.into(),
+            td_mode,
+            side,
+            pos_side: None,
+            ord_type,
+            sz,
+            px: None,
+            reduce_only: None,
+            cl_ord_id: None,
+            tag: None,
+            ccy: None,
+            attach_algo_ords: Vec::new(),
+        }
+    }
+
+    /// 市价单
+    pub fn market(inst_id: impl Into<String>, td_mode: TradeMode, side: Side, sz: Decimal) -> Self {
+        Self::new(inst_id, td_mode, side, OrdType::Market, sz)
+    }
+
+    /// 限价单
+    pub fn limit(
+        inst_id: impl Into<String>,
+        td_mode: TradeMode,
+        side: Side,
+        sz: Decimal,
+        px: Decimal,
+    ) -> Self {
+        let mut request = Self::new(inst_id, td_mode, side, OrdType::Limit, sz);
+        request.px = Some(px);
+        request
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), OkxError> {
+        if self.sz <= Decimal::ZERO {
+            return Err(OkxError::Request(format!("无效的委托数量: {}", self.sz)));
+        }
+        match self.px {
+            Some(px) if px <= Decimal::ZERO => {
+                return Err(OkxError::Request(format!("无效的委托价格: {}", px)));
+            }
+            None if self.ord_type.requires_px() => {
+                return Err(OkxError::Request(format!(
+                    "{} 订单需要指定委托价格",
+                    self.ord_type
+                )));
+            }
+            _ => {}
+        }
+        if let Some(id) = &self.cl_ord_id {
+            validate_id("clOrdId", id, 32)?;
+        }
+        if let Some(tag) = &self.tag {
+            validate_id("tag", tag, 16)?;
+        }
+        for algo in &self.attach_algo_ords {
+            algo.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// 修改订单请求，`ordId` 与 `clOrdId` 二选一
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmendOrderRequest {
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    #[serde(rename = "ordId", skip_serializing_if = "Option::is_none")]
+    pub ord_id: Option<String>,
+    #[serde(rename = "clOrdId", skip_serializing_if = "Option::is_none")]
+    pub cl_ord_id: Option<String>,
+    /// 客户自定义修改事件ID
+    #[serde(rename = "reqId", skip_serializing_if = "Option::is_none")]
+    pub req_id: Option<String>,
+    #[serde(rename = "newSz", skip_serializing_if = "Option::is_none")]
+    pub new_sz: Option<Decimal>,
+    #[serde(rename = "newPx", skip_serializing_if = "Option::is_none")]
+    pub new_px: Option<Decimal>,
+    /// 修改失败时是否自动撤单
+    #[serde(rename = "cxlOnFail", skip_serializing_if = "Option::is_none")]
+    pub cxl_on_fail: Option<bool>,
+}
+
+impl AmendOrderRequest {
+    /// 按订单ID修改
+    pub fn by_ord_id(inst_id: impl Into<String>, ord_id: impl Into<String>) -> Self {
+        Self {
+            inst_id: inst_id.into(),
+            ord_id: Some(ord_id.into()),
+            cl_ord_id: None,
+            req_id: None,
+            new_sz: None,
+            new_px: None,
+            cxl_on_fail: None,
+        }
+    }
+
+    /// 按客户自定义订单ID修改
+    pub fn by_cl_ord_id(inst_id: impl Into<String>, cl_ord_id: impl Into<String>) -> Self {
+        Self {
+            ord_id: None,
+            cl_ord_id: Some(cl_ord_id.into()),
+            ..Self::by_ord_id(inst_id, String::new())
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), OkxError> {
+        validate_order_ref(&self.ord_id, &self.cl_ord_id)?;
+        if self.new_sz.is_none() && self.new_px.is_none() {
+            return Err(OkxError::Request(
+                "修改订单需要指定新数量或新价格".to_string(),
+            ));
+        }
+        if let Some(sz) = self.new_sz.filter(|sz| *sz <= Decimal::ZERO) {
+            return Err(OkxError::Request(format!("无效的委托数量: {}", sz)));
+        }
+        if let Some(px) = self.new_px.filter(|px| *px <= Decimal::ZERO) {
+            return Err(OkxError::Request(format!("无效的委托价格: {}", px)));
+        }
+        if let Some(id) = &self.req_id {
+            validate_id("reqId", id, 32)?;
+        }
+        Ok(())
+    }
+}
+
+/// 撤单请求，`ordId` 与 `clOrdId` 二选一
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelOrderRequest {
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    #[serde(rename = "ordId", skip_serializing_if = "Option::is_none")]
+    pub ord_id: Option<String>,
+    #[serde(rename = "clOrdId", skip_serializing_if = "Option::is_none")]
+    pub cl_ord_id: Option<String>,
+}
+
+impl CancelOrderRequest {
+    /// 按订单ID撤单
+    pub fn by_ord_id(inst_id: impl Into<String>, ord_id: impl Into<String>) -> Self {
+        Self {
+            inst_id: inst_id.into(),
+            ord_id: Some(ord_id.into()),
+            cl_ord_id: None,
+        }
+    }
+
+    /// 按客户自定义订单ID撤单
+    pub fn by_cl_ord_id(inst_id: impl Into<String>, cl_ord_id: impl Into<String>) -> Self {
+        Self {
+            inst_id: inst_id.into(),
+            ord_id: None,
+            cl_ord_id: Some(cl_ord_id.into()),
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), OkxError> {
+        validate_order_ref(&self.ord_id, &self.cl_ord_id)
+    }
+}
+
+/// `ordId` 与 `clOrdId` 至少指定一个
+fn validate_order_ref(ord_id: &Option<String>, cl_ord_id: &Option<String>) -> Result<(), OkxError> {
+    let has = |id: &Option<String>| id.as_ref().is_some_and(|id| !id.is_empty());
+    if !has(ord_id) && !has(cl_ord_id) {
+        return Err(OkxError::Request("需要指定 ordId 或 clOrdId".to_string()));
+    }
+    if let Some(id) = cl_ord_id {
+        validate_id("clOrdId", id, 32)?;
+    }
+    Ok(())
+}
+
+/// 客户自定义ID：1 到 `max_len` 位字母或数字
+fn validate_id(label: &str, id: &str, max_len: usize) -> Result<(), OkxError> {
+    if id.is_empty() || id.len() > max_len || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(OkxError::Request(format!(
+            "无效的{}: {} (应为1-{}位字母或数字)",
+            label, id, max_len
+        )));
+    }
+    Ok(())
+}
+
+/// 下单、改单、撤单接口返回的单条结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderResult {
+    #[serde(rename = "ordId", default)]
+    pub ord_id: String,
+    #[serde(rename = "clOrdId", default)]
+    pub cl_ord_id: String,
+    #[serde(default)]
+    pub tag: String,
+    /// 修改订单时的客户自定义修改事件ID
+    #[serde(rename = "reqId", default)]
+    pub req_id: String,
+    /// 系统完成处理的时间
+    #[serde(default, with = "opt_timestamp_ms")]
+    pub ts: Option<DateTime<Utc>>,
+    #[serde(rename = "sCode")]
+    pub s_code: String,
+    #[serde(rename = "sMsg", default)]
+    pub s_msg: String,
+}
+
+impl OrderResult {
+    /// `sCode` 为0表示请求已被受理
+    pub fn is_success(&self) -> bool {
+        self.s_code == "0"
+    }
+}
+
+/// 单个订单操作的结果
+///
+/// 被交易所拒绝（`sCode` 非0）不作为错误返回，而是 [`OrderOutcome::Rejected`]，
+/// 调用方可以根据错误码决定是否重试或调整参数。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum OrderOutcome {
+    /// 已受理
+    Accepted {
+        #[serde(rename = "ordId")]
+        ord_id: String,
+        #[serde(rename = "clOrdId")]
+        cl_ord_id: String,
+        tag: String,
+        #[serde(with = "opt_timestamp_ms")]
+        ts: Option<DateTime<Utc>>,
+    },
+    /// 被拒绝
+    Rejected {
+        #[serde(rename = "ordId")]
+        ord_id: String,
+        #[serde(rename = "clOrdId")]
+        cl_ord_id: String,
+        #[serde(rename = "sCode")]
+        code: String,
+        #[serde(rename = "sMsg")]
+        msg: String,
+    },
+}
+
+impl OrderOutcome {
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, OrderOutcome::Accepted { .. })
+    }
+
+    /// 订单ID，下单被拒绝时为空
+    pub fn ord_id(&self) -> &str {
+        match self {
+            OrderOutcome::Accepted { ord_id, .. } | OrderOutcome::Rejected { ord_id, .. } => ord_id,
+        }
+    }
+
+    pub fn cl_ord_id(&self) -> &str {
+        match self {
+            OrderOutcome::Accepted { cl_ord_id, .. } | OrderOutcome::Rejected { cl_ord_id, .. } => {
+                cl_ord_id
+            }
+        }
+    }
+
+    /// 拒绝时的错误码
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            OrderOutcome::Accepted { .. } => None,
+            OrderOutcome::Rejected { code, .. } => Some(code),
+        }
+    }
+}
+
+impl From<OrderResult> for OrderOutcome {
+    fn from(result: OrderResult) -> Self {
+        if result.is_success() {
+            OrderOutcome::Accepted {
+                ord_id: result.ord_id,
+                cl_ord_id: result.cl_ord_id,
+                tag: result.tag,
+                ts: result.ts,
+            }
+        } else {
+            OrderOutcome::Rejected {
+                ord_id: result.ord_id,
+                cl_ord_id: result.cl_ord_id,
+                code: result.s_code,
+                msg: result.s_msg,
+            }
+        }
+    }
+}
+
+impl fmt::Display for OrderOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderOutcome::Accepted {
+                ord_id, cl_ord_id, ..
+            } => {
+                write!(f, "已受理 ordId={}", ord_id)?;
+                if !cl_ord_id.is_empty() {
+                    write!(f, " clOrdId={}", cl_ord_id)?;
+                }
+                Ok(())
+            }
+            OrderOutcome::Rejected {
+                ord_id, code, msg, ..
+            } => {
+                write!(f, "被拒绝 [{}] {}", code, msg)?;
+                if !ord_id.is_empty() {
+                    write!(f, " (ordId={})", ord_id)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl OkxClient {
+    /// 下单
+    ///
+    /// 订单被拒绝时返回 [`OrderOutcome::Rejected`]，只有请求本身失败才返回错误。
+    pub async fn place_order(&self, request: &PlaceOrderRequest) -> Result<OrderOutcome, OkxError> {
+        request.validate()?;
+        self.order_outcome("/api/v5/trade/order", request).await
+    }
+
+    /// 修改未完成订单的数量或价格
+    pub async fn amend_order(&self, request: &AmendOrderRequest) -> Result<OrderOutcome, OkxError> {
+        request.validate()?;
+        self.order_outcome("/api/v5/trade/amend-order", request)
+            .await
+    }
+
+    /// 撤单
+    pub async fn cancel_order(
+        &self,
+        request: &CancelOrderRequest,
+    ) -> Result<OrderOutcome, OkxError> {
+        request.validate()?;
+        self.order_outcome("/api/v5/trade/cancel-order", request)
+            .await
+    }
+
+    /// 发送单个订单操作，把 `data[0]` 的 `sCode/sMsg` 转换为结果
+    async fn order_outcome(
+        &self,
+        endpoint: &str,
+        body: &impl Serialize,
+    ) -> Result<OrderOutcome, OkxError> {
+        self.post_items::<ApiResponse<OrderResult>>(endpoint, Some(body))
+            .await?
+            .data
+            .into_iter()
+            .next()
+            .map(OrderOutcome::from)
+            .ok_or_else(|| OkxError::Request(format!("{} 未返回结果", endpoint)))
+    }
+}
+
+/// 批量接口单次请求的最大订单数
+pub const MAX_BATCH_SIZE: usize = 20;
+
+/// 可批量发送的订单请求
+trait BatchRequest: Serialize {
+    /// 客户自定义订单ID，返回条目数与请求不一致时用于对齐结果
+    fn cl_ord_id(&self) -> Option<&str>;
+}
+
+impl BatchRequest for PlaceOrderRequest {
+    fn cl_ord_id(&self) -> Option<&str> {
+        self.cl_ord_id.as_deref()
+    }
+}
+
+impl BatchRequest for AmendOrderRequest {
+    fn cl_ord_id(&self) -> Option<&str> {
+        self.cl_ord_id.as_deref()
+    }
+}
+
+impl BatchRequest for CancelOrderRequest {
+    fn cl_ord_id(&self) -> Option<&str> {
+        self.cl_ord_id.as_deref()
+    }
+}
+
+/// 批量操作中单个订单的结果
+///
+/// 参数校验失败或所在批次的请求失败时为 `Err`，同一批次的订单共享同一个错误。
+/// 批次请求失败（如网络超时）时订单可能已被处理，应查询订单状态后再决定是否重试。
+pub type BatchItemResult = Result<OrderOutcome, Arc<OkxError>>;
+
+impl OkxClient {
+    /// 批量下单，超过20个订单时分批请求
+    ///
+    /// 返回的结果与 `requests` 一一对应，部分订单被拒绝不影响其他订单。
+    pub async fn place_orders(&self, requests: &[PlaceOrderRequest]) -> Vec<BatchItemResult> {
+        self.batch_outcomes(
+            "/api/v5/trade/batch-orders",
+            requests,
+            PlaceOrderRequest::validate,
+        )
+        .await
+    }
+
+    /// 批量修改订单，超过20个订单时分批请求
+    pub async fn amend_orders(&self, requests: &[AmendOrderRequest]) -> Vec<BatchItemResult> {
+        self.batch_outcomes(
+            "/api/v5/trade/amend-batch-orders",
+            requests,
+            AmendOrderRequest::validate,
+        )
+        .await
+    }
+
+    /// 批量撤单，超过20个订单时分批请求
+    pub async fn cancel_orders(&self, requests: &[CancelOrderRequest]) -> Vec<BatchItemResult> {
+        self.batch_outcomes(
+            "/api/v5/trade/cancel-batch-orders",
+            requests,
+            CancelOrderRequest::validate,
+        )
+        .await
+    }
+
+    /// 校验、分批发送并按输入顺序汇总结果
+    ///
+    /// 各批次依次发送，由限速器按产品计入每个订单的配额。
+    async fn batch_outcomes<R: BatchRequest>(
+        &self,
+        endpoint: &str,
+        requests: &[R],
+        validate: impl Fn(&R) -> Result<(), OkxError>,
+    ) -> Vec<BatchItemResult> {
+        let (mut results, batches) = plan_batches(requests, validate);
+        for batch in batches {
+            let body: Vec<&R> = batch.iter().map(|&index| &requests[index]).collect();
+            let response = self
+                .post_items::<ApiResponse<OrderResult>>(endpoint, Some(&body))
+                .await;
+            let cl_ord_ids: Vec<Option<&str>> = body.iter().map(|r| r.cl_ord_id()).collect();
+            fill_batch(&mut results, &batch, &cl_ord_ids, endpoint, response);
+        }
+        results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| Err(Arc::new(OkxError::Request("订单未发送".to_string()))))
+            })
+            .collect()
+    }
+}
+
+/// 校验每个请求，返回预填了校验错误的结果列表，以及按 [`MAX_BATCH_SIZE`] 分组的有效请求下标
+fn plan_batches<R>(
+    requests: &[R],
+    validate: impl Fn(&R) -> Result<(), OkxError>,
+) -> (Vec<Option<BatchItemResult>>, Vec<Vec<usize>>) {
+    let mut results = Vec::with_capacity(requests.len());
+    let mut valid = Vec::new();
+    for (index, request) in requests.iter().enumerate() {
+        match validate(request) {
+            Ok(()) => {
+                results.push(None);
+                valid.push(index);
+            }
+            Err(err) => results.push(Some(Err(Arc::new(err)))),
+        }
+    }
+    let batches = valid
+        .chunks(MAX_BATCH_SIZE)
+        .map(|batch| batch.to_vec())
+        .collect();
+    (results, batches)
+}
+
+/// 把一个批次的响应写回对应下标
+///
+/// 正常情况下响应与请求按位置对应。条目数不一致时，若批次中每个订单都有 `clOrdId` 则按其对齐，
+/// 找不到结果的订单（或无法对齐的整批订单）标记为错误并提示核对订单状态，
+/// 因为这些订单可能已被处理。
+fn fill_batch(
+    results: &mut [Option<BatchItemResult>],
+    batch: &[usize],
+    cl_ord_ids: &[Option<&str>],
+    endpoint: &str,
+    response: Result<ApiResponse<OrderResult>, OkxError>,
+) {
+    let mut data = match response {
+        Ok(response) => response.data,
+        Err(err) => {
+            let err = Arc::new(err);
+            for &index in batch {
+                results[index] = Some(Err(err.clone()));
+            }
+            return;
+        }
+    };
+
+    if data.len() == batch.len() {
+        for (&index, result) in batch.iter().zip(data) {
+            results[index] = Some(Ok(result.into()));
+        }
+        return;
+    }
+
+    let err = Arc::new(OkxError::Request(format!(
+        "{} 返回 {} 条结果，与请求的 {} 个订单不一致，订单可能已被处理，请通过 orders-pending 核对",
+        endpoint,
+        data.len(),
+        batch.len()
+    )));
+    let by_cl_ord_id = cl_ord_ids
+        .iter()
+        .all(|id| id.is_some_and(|id| !id.is_empty()));
+    for (&index, cl_ord_id) in batch.iter().zip(cl_ord_ids) {
+        let matched = match cl_ord_id {
+            Some(id) if by_cl_ord_id => data
+                .iter()
+                .position(|result| result.cl_ord_id == *id)
+                .map(|position| data.remove(position)),
+            _ => None,
+        };
+        results[index] = Some(match matched {
+            Some(result) => Ok(result.into()),
+            None => Err(err.clone()),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn cancel(inst_id: &str, ord_id: &str) -> CancelOrderRequest {
+        CancelOrderRequest::by_ord_id(inst_id, ord_id)
+    }
+
+    #[test]
+    fn test_place_order_request_serialization() {
+        let mut request = PlaceOrderRequest::limit(
+            "BTC-USDT-SWAP",
+            TradeMode::Cross,
+            Side::Buy,
+            dec("2"),
+            dec("60000.5"),
+        );
+        request.pos_side = Some(PosSide::Long);
+        request.cl_ord_id = Some("rebal001".to_string());
+        request.attach_algo_ords = vec![AttachAlgoOrder::take_profit(dec("66000"), None)
+            .with_stop_loss(dec("57000"), Some(dec("56900")))];
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({
+                "instId": "BTC-USDT-SWAP",
+                "tdMode": "cross",
+                "side": "buy",
+                "posSide": "long",
+                "ordType": "limit",
+                "sz": "2",
+                "px": "60000.5",
+                "clOrdId": "rebal001",
+                "attachAlgoOrds": [{
+                    "tpTriggerPx": "66000",
+                    "tpOrdPx": "-1",
+                    "slTriggerPx": "57000",
+                    "slOrdPx": "56900"
+                }]
+            })
+        );
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_place_order_validation() {
+        let mut request = PlaceOrderRequest::new(
+            "BTC-USDT",
+            TradeMode::Cash,
+            Side::Sell,
+            OrdType::PostOnly,
+            dec("0.1"),
+        );
+        assert!(request.validate().is_err());
+
+        request.px = Some(dec("70000"));
+        assert!(request.validate().is_ok());
+
+        request.cl_ord_id = Some("bad-id".to_string());
+        assert!(request.validate().is_err());
+
+        let mut request =
+            PlaceOrderRequest::market("BTC-USDT", TradeMode::Cash, Side::Buy, dec("100"));
+        request.tag = Some("x".repeat(17));
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_amend_and_cancel_validation() {
+        let request = AmendOrderRequest::by_ord_id("BTC-USDT", "312269865356374016");
+        assert!(request.validate().is_err());
+
+        let mut request = AmendOrderRequest::by_cl_ord_id("BTC-USDT", "b15");
+        request.new_px = Some(dec("2.15"));
+        assert!(request.validate().is_ok());
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({"instId": "BTC-USDT", "clOrdId": "b15", "newPx": "2.15"})
+        );
+
+        let request = CancelOrderRequest {
+            inst_id: "BTC-USDT".to_string(),
+            ord_id: None,
+            cl_ord_id: None,
+        };
+        assert!(request.validate().is_err());
+        assert!(CancelOrderRequest::by_ord_id("BTC-USDT", "1")
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_order_outcome_from_result() {
+        let json = r#"{"code":"1","msg":"","data":[
+            {"clOrdId":"oktswap6","ordId":"","tag":"","ts":"1695190491421","sCode":"51008","sMsg":"Order failed. Insufficient USDT balance"}
+        ]}"#;
+        let response: ApiResponse<OrderResult> = serde_json::from_str(json).unwrap();
+        let outcome = OrderOutcome::from(response.data[0].clone());
+
+        assert!(!outcome.is_accepted());
+        assert_eq!(outcome.code(), Some("51008"));
+        assert_eq!(outcome.cl_ord_id(), "oktswap6");
+        assert!(outcome.to_string().contains("Insufficient USDT balance"));
+
+        let json = r#"{"clOrdId":"","ordId":"312269865356374016","tag":"","ts":"1695190491421","sCode":"0","sMsg":""}"#;
+        let outcome = OrderOutcome::from(serde_json::from_str::<OrderResult>(json).unwrap());
+        assert!(outcome.is_accepted());
+        assert_eq!(outcome.ord_id(), "312269865356374016");
+        assert_eq!(outcome.code(), None);
+    }
+
+    #[test]
+    fn test_plan_batches_chunks_valid_requests() {
+        let mut requests: Vec<CancelOrderRequest> = (0..45)
+            .map(|i| cancel("BTC-USDT", &i.to_string()))
+            .collect();
+        requests[3].ord_id = None;
+
+        let (results, batches) = plan_batches(&requests, CancelOrderRequest::validate);
+        assert_eq!(results.len(), 45);
+        assert!(matches!(results[3], Some(Err(_))));
+        assert_eq!(results.iter().filter(|r| r.is_none()).count(), 44);
+        assert_eq!(
+            batches.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![20, 20, 4]
+        );
+        assert!(!batches.concat().contains(&3));
+        assert_eq!(batches[1][0], 21);
+    }
+
+    #[test]
+    fn test_fill_batch_reports_partial_failures_per_order() {
+        let requests: Vec<CancelOrderRequest> =
+            (0..3).map(|i| cancel("ETH-USDT", &i.to_string())).collect();
+        let (mut results, batches) = plan_batches(&requests, CancelOrderRequest::validate);
+
+        let json = r#"{"code":"2","msg":"","data":[
+            {"ordId":"0","clOrdId":"","sCode":"0","sMsg":""},
+            {"ordId":"1","clOrdId":"","sCode":"51400","sMsg":"Cancellation failed as the order has been filled"},
+            {"ordId":"2","clOrdId":"","sCode":"0","sMsg":""}
+        ]}"#;
+        let response: ApiResponse<OrderResult> = serde_json::from_str(json).unwrap();
+        fill_batch(
+            &mut results,
+            &batches[0],
+            &[None, None, None],
+            "/api/v5/trade/cancel-batch-orders",
+            Ok(response),
+        );
+
+        let outcomes: Vec<OrderOutcome> =
+            results.into_iter().map(|r| r.unwrap().unwrap()).collect();
+        assert!(outcomes[0].is_accepted());
+        assert_eq!(outcomes[1].code(), Some("51400"));
+        assert_eq!(outcomes[1].ord_id(), "1");
+        assert!(outcomes[2].is_accepted());
+    }
+
+    #[test]
+    fn test_fill_batch_shares_request_error() {
+        let requests: Vec<CancelOrderRequest> =
+            (0..2).map(|i| cancel("ETH-USDT", &i.to_string())).collect();
+        let (mut results, batches) = plan_batches(&requests, CancelOrderRequest::validate);
+
+        let err = OkxError::Request("timeout".to_string());
+        fill_batch(
+            &mut results,
+            &batches[0],
+            &[None, None],
+            "/api/v5/trade/cancel-batch-orders",
+            Err(err),
+        );
+        for result in results {
+            assert!(result.unwrap().unwrap_err().to_string().contains("timeout"));
+        }
+
+        // 返回条目数不一致时无法对齐，整批视为失败
+        let (mut results, batches) = plan_batches(&requests, CancelOrderRequest::validate);
+        let response: ApiResponse<OrderResult> = serde_json::from_str(
+            r#"{"code":"0","msg":"","data":[{"ordId":"0","sCode":"0","sMsg":""}]}"#,
+        )
+        .unwrap();
+        fill_batch(
+            &mut results,
+            &batches[0],
+            &[None, None],
+            "/api/v5/trade/cancel-batch-orders",
+            Ok(response),
+        );
+        assert!(results.iter().all(|r| matches!(r, Some(Err(_)))));
+    }
+
+    #[test]
+    fn test_fill_batch_aligns_by_cl_ord_id_on_count_mismatch() {
+        let requests: Vec<CancelOrderRequest> = ["a1", "b2", "c3"]
+            .iter()
+            .map(|id| CancelOrderRequest::by_cl_ord_id("ETH-USDT", *id))
+            .collect();
+        let (mut results, batches) = plan_batches(&requests, CancelOrderRequest::validate);
+
+        let response: ApiResponse<OrderResult> = serde_json::from_str(
+            r#"{"code":"0","msg":"","data":[
+                {"ordId":"3","clOrdId":"c3","sCode":"0","sMsg":""},
+                {"ordId":"1","clOrdId":"a1","sCode":"0","sMsg":""}
+            ]}"#,
+        )
+        .unwrap();
+        fill_batch(
+            &mut results,
+            &batches[0],
+            &[Some("a1"), Some("b2"), Some("c3")],
+            "/api/v5/trade/cancel-batch-orders",
+            Ok(response),
+        );
+
+        let results: Vec<BatchItemResult> = results.into_iter().map(Option::unwrap).collect();
+        assert_eq!(results[0].as_ref().unwrap().ord_id(), "1");
+        assert!(results[1]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("orders-pending"));
+        assert_eq!(results[2].as_ref().unwrap().ord_id(), "3");
+    }
+
+    #[test]
+    fn test_batch_file_fixture() {
+        let requests: Vec<PlaceOrderRequest> =
+            serde_json::from_str(include_str!("../fixtures/batch_orders.json")).unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests.iter().all(|r| r.validate().is_ok()));
+        assert_eq!(
+            requests[1].attach_algo_ords[0].sl_ord_px,
+            Some(AttachAlgoOrder::MARKET_PX)
+        );
+        assert_eq!(requests[2].ord_type, OrdType::PostOnly);
+        assert_eq!(requests[2].pos_side, None);
+    }
+}
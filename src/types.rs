@@ -1,5 +1,16 @@
+use crate::time::{opt_timestamp_ms, timestamp_ms};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+pub mod enums;
+pub mod legacy;
+
+pub use enums::{
+    AccountLevel, BillSubType, BillType, CloseType, Direction, ExecType, InstType, MarginMode,
+    OrdType, PosSide, PositionMode, Side, TradeMode,
+};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiResponse<T> {
     pub code: String,
@@ -7,202 +18,374 @@ pub struct ApiResponse<T> {
     pub data: Vec<T>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// 历史持仓，数值字段为精确十进制，空字符串解析为 `None`
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PositionHistory {
     /// 产品类型
     #[serde(rename = "instType")]
-    pub inst_type: String,
+    pub inst_type: InstType,
     /// 交易产品ID
     #[serde(rename = "instId")]
     pub inst_id: String,
     /// 保证金模式
     #[serde(rename = "mgnMode")]
-    pub mgn_mode: String,
+    pub mgn_mode: MarginMode,
     /// 持仓ID
     #[serde(rename = "posId")]
     pub pos_id: String,
     /// 持仓方向
     #[serde(rename = "posSide")]
-    pub pos_side: String,
+    pub pos_side: PosSide,
     /// 开仓平均价
-    #[serde(rename = "openAvgPx")]
-    pub open_avg_px: String,
+    #[serde(rename = "openAvgPx", default, with = "decimal")]
+    pub open_avg_px: Option<Decimal>,
     /// 平仓平均价
-    #[serde(rename = "closeAvgPx")]
-    pub close_avg_px: String,
+    #[serde(rename = "closeAvgPx", default, with = "decimal")]
+    pub close_avg_px: Option<Decimal>,
     /// 已实现收益
-    #[serde(rename = "realizedPnl")]
-    pub realized_pnl: String,
+    #[serde(rename = "realizedPnl", default, with = "decimal")]
+    pub realized_pnl: Option<Decimal>,
     /// 平仓类型
     #[serde(rename = "type")]
-    pub close_type: String,
+    pub close_type: CloseType,
     /// 开仓时间
-    #[serde(rename = "cTime")]
-    pub open_time: String,
+    #[serde(rename = "cTime", with = "timestamp_ms")]
+    pub open_time: DateTime<Utc>,
     /// 仓位更新时间
-    #[serde(rename = "uTime")]
-    pub u_time: String,
+    #[serde(rename = "uTime", with = "timestamp_ms")]
+    pub u_time: DateTime<Utc>,
     /// 杠杆倍数
-    pub lever: String,
+    #[serde(default, with = "decimal")]
+    pub lever: Option<Decimal>,
     /// 手续费
-    pub fee: String,
+    #[serde(default, with = "decimal")]
+    pub fee: Option<Decimal>,
     /// 资金费用
-    #[serde(rename = "fundingFee")]
-    pub funding_fee: String,
+    #[serde(rename = "fundingFee", default, with = "decimal")]
+    pub funding_fee: Option<Decimal>,
     /// 方向
-    pub direction: String,
+    pub direction: Direction,
     /// 币种
     pub ccy: String,
     /// 平仓总持仓
-    #[serde(rename = "closeTotalPos")]
-    pub close_total_pos: String,
+    #[serde(rename = "closeTotalPos", default, with = "decimal")]
+    pub close_total_pos: Option<Decimal>,
     /// 开仓最大持仓
-    #[serde(rename = "openMaxPos")]
-    pub open_max_pos: String,
+    #[serde(rename = "openMaxPos", default, with = "decimal")]
+    pub open_max_pos: Option<Decimal>,
     /// 盈亏
-    pub pnl: String,
+    #[serde(default, with = "decimal")]
+    pub pnl: Option<Decimal>,
     /// 盈亏比例
-    #[serde(rename = "pnlRatio")]
-    pub pnl_ratio: String,
+    #[serde(rename = "pnlRatio", default, with = "decimal")]
+    pub pnl_ratio: Option<Decimal>,
     /// 强平惩罚
-    #[serde(rename = "liqPenalty")]
-    pub liq_penalty: String,
+    #[serde(rename = "liqPenalty", default, with = "decimal")]
+    pub liq_penalty: Option<Decimal>,
     /// 非结算平均价格
-    #[serde(rename = "nonSettleAvgPx")]
-    pub non_settle_avg_px: String,
+    #[serde(rename = "nonSettleAvgPx", default, with = "decimal")]
+    pub non_settle_avg_px: Option<Decimal>,
     /// 结算盈亏
-    #[serde(rename = "settledPnl")]
-    pub settled_pnl: String,
+    #[serde(rename = "settledPnl", default, with = "decimal")]
+    pub settled_pnl: Option<Decimal>,
     /// 触发价格
-    #[serde(rename = "triggerPx")]
-    pub trigger_px: String,
+    #[serde(rename = "triggerPx", default, with = "decimal")]
+    pub trigger_px: Option<Decimal>,
     /// 标的
     pub uly: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// 当前持仓，数值字段为精确十进制，空字符串解析为 `None`
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
     /// 产品类型
     #[serde(rename = "instType")]
-    pub inst_type: String,
+    pub inst_type: InstType,
     /// 交易产品ID
     #[serde(rename = "instId")]
     pub inst_id: String,
     /// 保证金模式
     #[serde(rename = "mgnMode")]
-    pub mgn_mode: String,
+    pub mgn_mode: MarginMode,
     /// 持仓ID
     #[serde(rename = "posId")]
     pub pos_id: String,
     /// 持仓方向
     #[serde(rename = "posSide")]
-    pub pos_side: String,
+    pub pos_side: PosSide,
     /// 持仓数量
-    pub pos: String,
+    #[serde(default, with = "decimal")]
+    pub pos: Option<Decimal>,
     /// 开仓平均价
-    #[serde(rename = "avgPx")]
-    pub avg_px: String,
+    #[serde(rename = "avgPx", default, with = "decimal")]
+    pub avg_px: Option<Decimal>,
     /// 未实现收益
-    #[serde(rename = "upl")]
-    pub upl: String,
+    #[serde(rename = "upl", default, with = "decimal")]
+    pub upl: Option<Decimal>,
     /// 未实现收益率
-    #[serde(rename = "uplRatio")]
-    pub upl_ratio: String,
+    #[serde(rename = "uplRatio", default, with = "decimal")]
+    pub upl_ratio: Option<Decimal>,
     /// 杠杆倍数
-    pub lever: String,
+    #[serde(default, with = "decimal")]
+    pub lever: Option<Decimal>,
     /// 标记价格
-    #[serde(rename = "markPx")]
-    pub mark_px: String,
+    #[serde(rename = "markPx", default, with = "decimal")]
+    pub mark_px: Option<Decimal>,
     /// 最新成交价
-    #[serde(rename = "lastPx")]
-    pub last_px: Option<String>,
+    #[serde(rename = "lastPx", default, with = "decimal")]
+    pub last_px: Option<Decimal>,
     /// 最新成交价时间
-    #[serde(rename = "lastPxTime")]
-    pub last_px_time: Option<String>,
+    #[serde(rename = "lastPxTime", default, with = "opt_timestamp_ms")]
+    pub last_px_time: Option<DateTime<Utc>>,
     /// 持仓价值
-    #[serde(rename = "posValue")]
-    pub pos_value: Option<String>,
+    #[serde(rename = "posValue", default, with = "decimal")]
+    pub pos_value: Option<Decimal>,
     /// 保证金
-    pub margin: Option<String>,
+    #[serde(default, with = "decimal")]
+    pub margin: Option<Decimal>,
     /// 保证金率
-    #[serde(rename = "mgnRatio")]
-    pub mgn_ratio: Option<String>,
+    #[serde(rename = "mgnRatio", default, with = "decimal")]
+    pub mgn_ratio: Option<Decimal>,
     /// 维持保证金率
-    #[serde(rename = "maintMarginRatio")]
-    pub maint_margin_ratio: Option<String>,
+    #[serde(rename = "maintMarginRatio", default, with = "decimal")]
+    pub maint_margin_ratio: Option<Decimal>,
     /// 强平价格
-    #[serde(rename = "liqPx")]
-    pub liq_px: Option<String>,
+    #[serde(rename = "liqPx", default, with = "decimal")]
+    pub liq_px: Option<Decimal>,
     /// 利息
-    pub interest: Option<String>,
+    #[serde(default, with = "decimal")]
+    pub interest: Option<Decimal>,
     /// 资金费用
-    #[serde(rename = "fundingFee")]
-    pub funding_fee: Option<String>,
+    #[serde(rename = "fundingFee", default, with = "decimal")]
+    pub funding_fee: Option<Decimal>,
     /// 资金费用时间
-    #[serde(rename = "fundingTime")]
-    pub funding_time: Option<String>,
+    #[serde(rename = "fundingTime", default, with = "opt_timestamp_ms")]
+    pub funding_time: Option<DateTime<Utc>>,
     /// 方向
-    pub direction: Option<String>,
+    pub direction: Option<Direction>,
     /// 币种
     pub ccy: Option<String>,
     /// 持仓时间
-    #[serde(rename = "cTime")]
-    pub c_time: String,
+    #[serde(rename = "cTime", with = "timestamp_ms")]
+    pub c_time: DateTime<Utc>,
     /// 仓位更新时间
-    #[serde(rename = "uTime")]
-    pub u_time: String,
+    #[serde(rename = "uTime", with = "timestamp_ms")]
+    pub u_time: DateTime<Utc>,
     /// 标的
     pub uly: Option<String>,
     /// 期权价值
-    #[serde(rename = "optVal")]
-    pub opt_val: Option<String>,
+    #[serde(rename = "optVal", default, with = "decimal")]
+    pub opt_val: Option<Decimal>,
     /// 期权价值时间
-    #[serde(rename = "optValTime")]
-    pub opt_val_time: Option<String>,
+    #[serde(rename = "optValTime", default, with = "opt_timestamp_ms")]
+    pub opt_val_time: Option<DateTime<Utc>>,
     /// 期权价值标记价格
-    #[serde(rename = "optValMarkPx")]
-    pub opt_val_mark_px: Option<String>,
+    #[serde(rename = "optValMarkPx", default, with = "decimal")]
+    pub opt_val_mark_px: Option<Decimal>,
     /// 期权价值最新价格
-    #[serde(rename = "optValLastPx")]
-    pub opt_val_last_px: Option<String>,
+    #[serde(rename = "optValLastPx", default, with = "decimal")]
+    pub opt_val_last_px: Option<Decimal>,
     /// 期权价值最新价格时间
-    #[serde(rename = "optValLastPxTime")]
-    pub opt_val_last_px_time: Option<String>,
+    #[serde(rename = "optValLastPxTime", default, with = "opt_timestamp_ms")]
+    pub opt_val_last_px_time: Option<DateTime<Utc>>,
     /// 期权价值持仓价值
-    #[serde(rename = "optValPosValue")]
-    pub opt_val_pos_value: Option<String>,
+    #[serde(rename = "optValPosValue", default, with = "decimal")]
+    pub opt_val_pos_value: Option<Decimal>,
     /// 期权价值保证金
-    #[serde(rename = "optValMargin")]
-    pub opt_val_margin: Option<String>,
+    #[serde(rename = "optValMargin", default, with = "decimal")]
+    pub opt_val_margin: Option<Decimal>,
     /// 期权价值保证金率
-    #[serde(rename = "optValMgnRatio")]
-    pub opt_val_mgn_ratio: Option<String>,
+    #[serde(rename = "optValMgnRatio", default, with = "decimal")]
+    pub opt_val_mgn_ratio: Option<Decimal>,
     /// 期权价值维持保证金率
-    #[serde(rename = "optValMaintMarginRatio")]
-    pub opt_val_maint_margin_ratio: Option<String>,
+    #[serde(rename = "optValMaintMarginRatio", default, with = "decimal")]
+    pub opt_val_maint_margin_ratio: Option<Decimal>,
     /// 期权价值强平价格
-    #[serde(rename = "optValLiqPx")]
-    pub opt_val_liq_px: Option<String>,
+    #[serde(rename = "optValLiqPx", default, with = "decimal")]
+    pub opt_val_liq_px: Option<Decimal>,
     /// 期权价值利息
-    #[serde(rename = "optValInterest")]
-    pub opt_val_interest: Option<String>,
+    #[serde(rename = "optValInterest", default, with = "decimal")]
+    pub opt_val_interest: Option<Decimal>,
     /// 期权价值资金费用
-    #[serde(rename = "optValFundingFee")]
-    pub opt_val_funding_fee: Option<String>,
+    #[serde(rename = "optValFundingFee", default, with = "decimal")]
+    pub opt_val_funding_fee: Option<Decimal>,
     /// 期权价值资金费用时间
-    #[serde(rename = "optValFundingTime")]
-    pub opt_val_funding_time: Option<String>,
+    #[serde(rename = "optValFundingTime", default, with = "opt_timestamp_ms")]
+    pub opt_val_funding_time: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PositionsParams {
     /// 产品类型
     #[serde(rename = "instType")]
-    pub inst_type: Option<String>,
+    pub inst_type: Option<InstType>,
     /// 交易产品ID
     #[serde(rename = "instId")]
     pub inst_id: Option<String>,
     /// 持仓ID
     #[serde(rename = "posId")]
     pub pos_id: Option<String>,
-} 
\ No newline at end of file
+}
+
+impl PositionHistory {
+    /// 转换为字符串字段版本，兼容尚未迁移的代码
+    pub fn to_legacy(&self) -> legacy::PositionHistory {
+        self.into()
+    }
+}
+
+impl Position {
+    /// 转换为字符串字段版本，兼容尚未迁移的代码
+    pub fn to_legacy(&self) -> legacy::Position {
+        self.into()
+    }
+}
+
+/// 把可选十进制数格式化为字符串，`None` 输出空字符串
+pub fn format_decimal(value: &Option<Decimal>) -> String {
+    value.map(|v| v.normalize().to_string()).unwrap_or_default()
+}
+
+/// OKX数值字段的序列化：字符串形式的十进制数，空字符串或 `null` 对应 `None`
+pub mod decimal {
+    use rust_decimal::Decimal;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use std::str::FromStr;
+
+    pub fn serialize<S>(value: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(value) => serializer.serialize_str(&value.to_string()),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(s) if !s.trim().is_empty() => parse(s.trim()).map(Some).map_err(de::Error::custom),
+            _ => Ok(None),
+        }
+    }
+
+    /// 解析十进制字符串，兼容科学计数法（如 `1e-8`）
+    pub fn parse(s: &str) -> Result<Decimal, rust_decimal::Error> {
+        Decimal::from_str(s).or_else(|_| Decimal::from_scientific(s))
+    }
+}
+
+/// 可选枚举字段的序列化：空字符串或 `null` 对应 `None`
+pub mod opt_enum {
+    use serde::de::{DeserializeOwned, IntoDeserializer};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        match value {
+            Some(value) => value.serialize(serializer),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: DeserializeOwned,
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(s) if !s.trim().is_empty() => T::deserialize(s.into_deserializer()).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn position_json(avg_px: &str, liq_px: &str) -> String {
+        format!(
+            r#"{{
+                "instType": "SWAP", "instId": "BTC-USDT-SWAP", "mgnMode": "cross",
+                "posId": "1", "posSide": "long", "pos": "1", "avgPx": "{}",
+                "upl": "0.1", "uplRatio": "0.0001", "lever": "10", "markPx": "50000.1",
+                "liqPx": "{}", "cTime": "1619507758793", "uTime": "1619507761462"
+            }}"#,
+            avg_px, liq_px
+        )
+    }
+
+    #[test]
+    fn test_position_decimal_fields() {
+        let position: Position = serde_json::from_str(&position_json("0.1", "")).unwrap();
+
+        // 0.1 + 0.2 用浮点数会得到 0.30000000000000004
+        assert_eq!(
+            position.avg_px.unwrap() + Decimal::from_str("0.2").unwrap(),
+            Decimal::from_str("0.3").unwrap()
+        );
+        assert_eq!(position.lever, Some(Decimal::from(10)));
+        assert_eq!(position.liq_px, None);
+        assert_eq!(position.margin, None);
+    }
+
+    #[test]
+    fn test_decimal_serialization_round_trip() {
+        let position: Position = serde_json::from_str(&position_json("50123.45", "")).unwrap();
+        let json = serde_json::to_value(&position).unwrap();
+
+        assert_eq!(json["avgPx"], "50123.45");
+        assert_eq!(json["liqPx"], "");
+
+        let again: Position = serde_json::from_value(json).unwrap();
+        assert_eq!(again.avg_px, position.avg_px);
+    }
+
+    #[test]
+    fn test_decimal_scientific_notation() {
+        assert_eq!(
+            decimal::parse("1e-8").unwrap(),
+            Decimal::from_str("0.00000001").unwrap()
+        );
+        assert!(decimal::parse("abc").is_err());
+    }
+
+    #[test]
+    fn test_legacy_conversion() {
+        let position: Position = serde_json::from_str(&position_json("50123.45", "")).unwrap();
+        let legacy = position.to_legacy();
+
+        assert_eq!(legacy.avg_px, "50123.45");
+        assert_eq!(legacy.lever, "10");
+        assert_eq!(legacy.liq_px, Some(String::new()));
+        assert_eq!(legacy.c_time, "1619507758793");
+        assert_eq!(legacy.direction, None);
+
+        let history: PositionHistory = serde_json::from_str(
+            r#"{
+                "instType": "SWAP", "instId": "BTC-USDT-SWAP", "mgnMode": "isolated",
+                "posId": "1", "posSide": "short", "openAvgPx": "42000.50", "closeAvgPx": "",
+                "realizedPnl": "-1.5", "type": "2", "cTime": "1619507758793",
+                "uTime": "1619507761462", "lever": "5", "direction": "short",
+                "ccy": "USDT", "uly": "BTC-USDT"
+            }"#,
+        )
+        .unwrap();
+        let legacy = history.to_legacy();
+        assert_eq!(legacy.mgn_mode, "isolated");
+        assert_eq!(legacy.close_type, "2");
+        assert_eq!(legacy.open_avg_px, "42000.50");
+        assert_eq!(legacy.close_avg_px, "");
+        assert_eq!(legacy.u_time, "1619507761462");
+        assert_eq!(format_decimal(&position.mark_px), "50000.1");
+        assert_eq!(format_decimal(&position.liq_px), "");
+    }
+}
@@ -0,0 +1,497 @@
+//! OKX 枚举字段
+//!
+//! 反序列化时遇到未知取值会落入 `Unknown(String)`，避免OKX新增取值导致解析失败；
+//! `FromStr` 用于解析命令行参数，遇到未知取值会返回列出可选值的错误。
+
+use anyhow::anyhow;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// 生成字符串枚举：`变体 => "OKX取值" [| "命令行别名"...]`
+macro_rules! okx_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $label:expr, {
+            $($(#[$vmeta:meta])* $variant:ident => $value:expr $(, $alias:expr)*;)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$vmeta])* $variant,)+
+            /// 未知取值（OKX新增）
+            Unknown(String),
+        }
+
+        impl $name {
+            /// 所有已知取值
+            pub const VALUES: &'static [&'static str] = &[$($value),+];
+
+            /// OKX接口中的取值
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)+
+                    $name::Unknown(value) => value,
+                }
+            }
+
+            /// 按OKX取值转换，未知取值返回 `Unknown`
+            pub fn from_okx(value: &str) -> Self {
+                match value {
+                    $($value => $name::$variant,)+
+                    other => $name::Unknown(other.to_string()),
+                }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = anyhow::Error;
+
+            fn from_str(s: &str) -> anyhow::Result<Self> {
+                let input = s.trim();
+                $(
+                    if input.eq_ignore_ascii_case($value)
+                        $(|| input.eq_ignore_ascii_case($alias))*
+                    {
+                        return Ok($name::$variant);
+                    }
+                )+
+                Err(anyhow!(
+                    "无效的{}: {} (可选: {})",
+                    $label,
+                    input,
+                    Self::VALUES.join(", ")
+                ))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Ok($name::from_okx(&value))
+            }
+        }
+    };
+}
+
+okx_enum! {
+    /// 产品类型
+    InstType, "产品类型", {
+        /// 币币
+        Spot => "SPOT";
+        /// 币币杠杆
+        Margin => "MARGIN";
+        /// 永续合约
+        Swap => "SWAP";
+        /// 交割合约
+        Futures => "FUTURES";
+        /// 期权
+        Option => "OPTION";
+    }
+}
+
+okx_enum! {
+    /// 保证金模式
+    MarginMode, "保证金模式", {
+        /// 全仓
+        Cross => "cross";
+        /// 逐仓
+        Isolated => "isolated";
+    }
+}
+
+okx_enum! {
+    /// 交易模式（下单时的 `tdMode`）
+    TradeMode, "交易模式", {
+        /// 全仓
+        Cross => "cross";
+        /// 逐仓
+        Isolated => "isolated";
+        /// 非保证金（现货模式下的币币、期权买方）
+        Cash => "cash";
+        /// 现货逐仓（带单员币币带单）
+        SpotIsolated => "spot_isolated", "spot-isolated";
+    }
+}
+
+okx_enum! {
+    /// 订单方向
+    Side, "订单方向", {
+        /// 买入
+        Buy => "buy";
+        /// 卖出
+        Sell => "sell";
+    }
+}
+
+okx_enum! {
+    /// 订单类型
+    OrdType, "订单类型", {
+        /// 市价单
+        Market => "market";
+        /// 限价单
+        Limit => "limit";
+        /// 只做maker单
+        PostOnly => "post_only", "post-only";
+        /// 全部成交或立即取消
+        Fok => "fok";
+        /// 立即成交并取消剩余
+        Ioc => "ioc";
+        /// 市价委托立即成交并取消剩余（仅适用交割、永续）
+        OptimalLimitIoc => "optimal_limit_ioc", "optimal-limit-ioc";
+    }
+}
+
+impl OrdType {
+    /// 是否需要指定委托价格
+    pub fn requires_px(&self) -> bool {
+        matches!(
+            self,
+            OrdType::Limit | OrdType::PostOnly | OrdType::Fok | OrdType::Ioc
+        )
+    }
+}
+
+okx_enum! {
+    /// 流动性方向（成交的 `execType`）
+    ExecType, "流动性方向", {
+        /// 挂单成交
+        Maker => "M", "maker";
+        /// 吃单成交
+        Taker => "T", "taker";
+    }
+}
+
+okx_enum! {
+    /// 持仓方向
+    PosSide, "持仓方向", {
+        /// 开平仓模式下的多头
+        Long => "long";
+        /// 开平仓模式下的空头
+        Short => "short";
+        /// 买卖模式
+        Net => "net";
+    }
+}
+
+okx_enum! {
+    /// 历史持仓的最近一次平仓类型
+    CloseType, "平仓类型", {
+        /// 1：部分平仓
+        PartiallyClosed => "1", "partial";
+        /// 2：完全平仓
+        FullyClosed => "2", "full";
+        /// 3：强平
+        Liquidation => "3", "liquidation";
+        /// 4：强减
+        PartialLiquidation => "4", "partial-liquidation";
+        /// 5：ADL自动减仓
+        Adl => "5", "adl";
+    }
+}
+
+okx_enum! {
+    /// 持仓方向（历史持仓、买卖模式下的实际方向）
+    Direction, "方向", {
+        /// 多
+        Long => "long";
+        /// 空
+        Short => "short";
+    }
+}
+
+okx_enum! {
+    /// 账户模式
+    AccountLevel, "账户模式", {
+        /// 1：现货模式（简单交易模式）
+        Spot => "1", "spot", "simple";
+        /// 2：合约模式（单币种保证金）
+        Futures => "2", "futures", "single-currency";
+        /// 3：跨币种保证金模式
+        MultiCurrency => "3", "multi-currency";
+        /// 4：组合保证金模式
+        Portfolio => "4", "portfolio";
+    }
+}
+
+impl AccountLevel {
+    /// 中文名称
+    pub fn description(&self) -> &str {
+        match self {
+            AccountLevel::Spot => "现货模式",
+            AccountLevel::Futures => "合约模式",
+            AccountLevel::MultiCurrency => "跨币种保证金模式",
+            AccountLevel::Portfolio => "组合保证金模式",
+            AccountLevel::Unknown(value) => value,
+        }
+    }
+}
+
+okx_enum! {
+    /// 持仓模式
+    PositionMode, "持仓模式", {
+        /// 开平仓模式，持仓区分多空
+        LongShort => "long_short_mode", "long-short", "long_short";
+        /// 买卖模式
+        Net => "net_mode", "net";
+    }
+}
+
+impl PositionMode {
+    /// 中文名称
+    pub fn description(&self) -> &str {
+        match self {
+            PositionMode::LongShort => "开平仓模式",
+            PositionMode::Net => "买卖模式",
+            PositionMode::Unknown(value) => value,
+        }
+    }
+}
+
+okx_enum! {
+    /// 账单类型
+    BillType, "账单类型", {
+        /// 1：划转
+        Transfer => "1", "transfer";
+        /// 2：交易
+        Trade => "2", "trade";
+        /// 3：交割
+        Delivery => "3", "delivery";
+        /// 4：自动换币
+        AutoConversion => "4", "auto-conversion";
+        /// 5：强平
+        Liquidation => "5", "liquidation";
+        /// 6：保证金划转
+        MarginTransfer => "6", "margin-transfer";
+        /// 7：扣息
+        Interest => "7", "interest";
+        /// 8：资金费
+        FundingFee => "8", "funding-fee";
+        /// 9：自动减仓
+        Adl => "9", "adl";
+        /// 10：穿仓补偿
+        Clawback => "10", "clawback";
+        /// 11：系统换币
+        SystemConversion => "11", "system-conversion";
+        /// 12：策略划拨
+        StrategyTransfer => "12", "strategy-transfer";
+        /// 13：对冲减仓
+        Ddh => "13", "ddh";
+        /// 14：大宗交易
+        BlockTrade => "14", "block-trade";
+        /// 15：一键借币
+        QuickMargin => "15", "quick-margin";
+        /// 16：借币
+        Borrow => "16", "borrow";
+        /// 22：还币
+        Repay => "22", "repay";
+        /// 24：价差交易
+        SpreadTrading => "24", "spread";
+        /// 26：结构化产品
+        StructuredProducts => "26", "structured";
+        /// 27：闪兑
+        Convert => "27", "convert";
+        /// 28：小额兑换
+        EasyConvert => "28", "easy-convert";
+        /// 29：一键还债
+        OneClickRepay => "29", "one-click-repay";
+        /// 30：简单交易
+        SimpleTrade => "30", "simple-trade";
+        /// 32：移仓
+        MovePosition => "32", "move-position";
+        /// 33：借贷
+        Loans => "33", "loans";
+        /// 34：结算
+        Settlement => "34", "settlement";
+        /// 250：带单员分润支出
+        ProfitSharingExpense => "250", "profit-sharing-expense";
+        /// 251：带单员分润退还
+        ProfitSharingRefund => "251", "profit-sharing-refund";
+    }
+}
+
+impl BillType {
+    /// 中文名称
+    pub fn description(&self) -> &str {
+        match self {
+            BillType::Transfer => "划转",
+            BillType::Trade => "交易",
+            BillType::Delivery => "交割",
+            BillType::AutoConversion => "自动换币",
+            BillType::Liquidation => "强平",
+            BillType::MarginTransfer => "保证金划转",
+            BillType::Interest => "扣息",
+            BillType::FundingFee => "资金费",
+            BillType::Adl => "自动减仓",
+            BillType::Clawback => "穿仓补偿",
+            BillType::SystemConversion => "系统换币",
+            BillType::StrategyTransfer => "策略划拨",
+            BillType::Ddh => "对冲减仓",
+            BillType::BlockTrade => "大宗交易",
+            BillType::QuickMargin => "一键借币",
+            BillType::Borrow => "借币",
+            BillType::Repay => "还币",
+            BillType::SpreadTrading => "价差交易",
+            BillType::StructuredProducts => "结构化产品",
+            BillType::Convert => "闪兑",
+            BillType::EasyConvert => "小额兑换",
+            BillType::OneClickRepay => "一键还债",
+            BillType::SimpleTrade => "简单交易",
+            BillType::MovePosition => "移仓",
+            BillType::Loans => "借贷",
+            BillType::Settlement => "结算",
+            BillType::ProfitSharingExpense => "分润支出",
+            BillType::ProfitSharingRefund => "分润退还",
+            BillType::Unknown(value) => value,
+        }
+    }
+}
+
+okx_enum! {
+    /// 账单子类型（常用取值，其余落入 `Unknown`）
+    BillSubType, "账单子类型", {
+        /// 1：买入
+        Buy => "1", "buy";
+        /// 2：卖出
+        Sell => "2", "sell";
+        /// 3：开多
+        OpenLong => "3", "open-long";
+        /// 4：开空
+        OpenShort => "4", "open-short";
+        /// 5：平多
+        CloseLong => "5", "close-long";
+        /// 6：平空
+        CloseShort => "6", "close-short";
+        /// 9：市场借币扣息
+        MarketLoanInterest => "9", "market-loan-interest";
+        /// 11：转入
+        TransferIn => "11", "transfer-in";
+        /// 12：转出
+        TransferOut => "12", "transfer-out";
+        /// 14：尊享借币扣息
+        VipLoanInterest => "14", "vip-loan-interest";
+        /// 100：强减平多
+        PartialLiquidationCloseLong => "100", "partial-liquidation-close-long";
+        /// 101：强减平空
+        PartialLiquidationCloseShort => "101", "partial-liquidation-close-short";
+        /// 102：强减买入
+        PartialLiquidationBuy => "102", "partial-liquidation-buy";
+        /// 103：强减卖出
+        PartialLiquidationSell => "103", "partial-liquidation-sell";
+        /// 104：强平平多
+        LiquidationLong => "104", "liquidation-long";
+        /// 105：强平平空
+        LiquidationShort => "105", "liquidation-short";
+        /// 106：强平买入
+        LiquidationBuy => "106", "liquidation-buy";
+        /// 107：强平卖出
+        LiquidationSell => "107", "liquidation-sell";
+        /// 110：强平换币转入
+        LiquidationTransferIn => "110", "liquidation-transfer-in";
+        /// 111：强平换币转出
+        LiquidationTransferOut => "111", "liquidation-transfer-out";
+        /// 112：交割平多
+        DeliveryLong => "112", "delivery-long";
+        /// 113：交割平空
+        DeliveryShort => "113", "delivery-short";
+        /// 125：自动减仓平多
+        AdlCloseLong => "125", "adl-close-long";
+        /// 126：自动减仓平空
+        AdlCloseShort => "126", "adl-close-short";
+        /// 127：自动减仓买入
+        AdlBuy => "127", "adl-buy";
+        /// 128：自动减仓卖出
+        AdlSell => "128", "adl-sell";
+        /// 160：手动追加保证金
+        ManualMarginIncrease => "160", "margin-increase";
+        /// 161：手动减少保证金
+        ManualMarginDecrease => "161", "margin-decrease";
+        /// 162：自动追加保证金
+        AutoMarginIncrease => "162", "auto-margin-increase";
+        /// 170：到期行权
+        Exercised => "170", "exercised";
+        /// 171：到期被行权
+        CounterpartyExercised => "171", "counterparty-exercised";
+        /// 172：到期作废
+        ExpiredOtm => "172", "expired-otm";
+        /// 173：资金费支出
+        FundingFeeExpense => "173", "funding-fee-expense";
+        /// 174：资金费收入
+        FundingFeeIncome => "174", "funding-fee-income";
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde_mapping() {
+        assert_eq!(serde_json::to_string(&InstType::Swap).unwrap(), r#""SWAP""#);
+        assert_eq!(
+            serde_json::from_str::<MarginMode>(r#""isolated""#).unwrap(),
+            MarginMode::Isolated
+        );
+        assert_eq!(
+            serde_json::from_str::<CloseType>(r#""3""#).unwrap(),
+            CloseType::Liquidation
+        );
+    }
+
+    #[test]
+    fn test_unknown_fallback() {
+        let value: InstType = serde_json::from_str(r#""EVENTS""#).unwrap();
+        assert_eq!(value, InstType::Unknown("EVENTS".to_string()));
+        assert_eq!(serde_json::to_string(&value).unwrap(), r#""EVENTS""#);
+
+        let value: CloseType = serde_json::from_str(r#""6""#).unwrap();
+        assert_eq!(value.as_str(), "6");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("swap".parse::<InstType>().unwrap(), InstType::Swap);
+        assert_eq!("Cross".parse::<MarginMode>().unwrap(), MarginMode::Cross);
+        assert_eq!("2".parse::<CloseType>().unwrap(), CloseType::FullyClosed);
+        assert_eq!("adl".parse::<CloseType>().unwrap(), CloseType::Adl);
+        assert_eq!("net".parse::<PosSide>().unwrap(), PosSide::Net);
+        assert_eq!(
+            "portfolio".parse::<AccountLevel>().unwrap(),
+            AccountLevel::Portfolio
+        );
+        assert_eq!("net".parse::<PositionMode>().unwrap(), PositionMode::Net);
+        assert_eq!("post-only".parse::<OrdType>().unwrap(), OrdType::PostOnly);
+        assert_eq!("SELL".parse::<Side>().unwrap(), Side::Sell);
+        assert_eq!(
+            "funding-fee".parse::<BillType>().unwrap(),
+            BillType::FundingFee
+        );
+        assert_eq!("174".parse::<BillSubType>().unwrap(), BillSubType::FundingFeeIncome);
+        assert_eq!(
+            "long_short_mode".parse::<PositionMode>().unwrap(),
+            PositionMode::LongShort
+        );
+    }
+
+    #[test]
+    fn test_from_str_error_lists_values() {
+        let err = "swp".parse::<InstType>().unwrap_err().to_string();
+        assert!(err.contains("swp"));
+        assert!(err.contains("SPOT, MARGIN, SWAP, FUTURES, OPTION"));
+
+        assert!("7".parse::<CloseType>().is_err());
+    }
+}
@@ -0,0 +1,292 @@
+//! 迁移兼容层：数值字段仍为 `String` 的持仓结构
+//!
+//! [`crate::types::Position`] 和 [`crate::types::PositionHistory`] 的数值字段已改为
+//! `Option<Decimal>`。仍按字符串处理数据的代码可以把导入路径改为本模块，
+//! 或通过 `to_legacy()` / `From` 转换，待迁移完成后再切换到十进制类型。
+//! 空值会转换为空字符串，与OKX原始响应一致。
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// 字符串字段版本的历史持仓
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionHistory {
+    /// 产品类型
+    #[serde(rename = "instType")]
+    pub inst_type: String,
+    /// 交易产品ID
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    /// 保证金模式
+    #[serde(rename = "mgnMode")]
+    pub mgn_mode: String,
+    /// 持仓ID
+    #[serde(rename = "posId")]
+    pub pos_id: String,
+    /// 持仓方向
+    #[serde(rename = "posSide")]
+    pub pos_side: String,
+    /// 开仓平均价
+    #[serde(rename = "openAvgPx")]
+    pub open_avg_px: String,
+    /// 平仓平均价
+    #[serde(rename = "closeAvgPx")]
+    pub close_avg_px: String,
+    /// 已实现收益
+    #[serde(rename = "realizedPnl")]
+    pub realized_pnl: String,
+    /// 平仓类型
+    #[serde(rename = "type")]
+    pub close_type: String,
+    /// 开仓时间
+    #[serde(rename = "cTime")]
+    pub open_time: String,
+    /// 仓位更新时间
+    #[serde(rename = "uTime")]
+    pub u_time: String,
+    /// 杠杆倍数
+    pub lever: String,
+    /// 手续费
+    pub fee: String,
+    /// 资金费用
+    #[serde(rename = "fundingFee")]
+    pub funding_fee: String,
+    /// 方向
+    pub direction: String,
+    /// 币种
+    pub ccy: String,
+    /// 平仓总持仓
+    #[serde(rename = "closeTotalPos")]
+    pub close_total_pos: String,
+    /// 开仓最大持仓
+    #[serde(rename = "openMaxPos")]
+    pub open_max_pos: String,
+    /// 盈亏
+    pub pnl: String,
+    /// 盈亏比例
+    #[serde(rename = "pnlRatio")]
+    pub pnl_ratio: String,
+    /// 强平惩罚
+    #[serde(rename = "liqPenalty")]
+    pub liq_penalty: String,
+    /// 非结算平均价格
+    #[serde(rename = "nonSettleAvgPx")]
+    pub non_settle_avg_px: String,
+    /// 结算盈亏
+    #[serde(rename = "settledPnl")]
+    pub settled_pnl: String,
+    /// 触发价格
+    #[serde(rename = "triggerPx")]
+    pub trigger_px: String,
+    /// 标的
+    pub uly: String,
+}
+
+/// 字符串字段版本的当前持仓
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    /// 产品类型
+    #[serde(rename = "instType")]
+    pub inst_type: String,
+    /// 交易产品ID
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    /// 保证金模式
+    #[serde(rename = "mgnMode")]
+    pub mgn_mode: String,
+    /// 持仓ID
+    #[serde(rename = "posId")]
+    pub pos_id: String,
+    /// 持仓方向
+    #[serde(rename = "posSide")]
+    pub pos_side: String,
+    /// 持仓数量
+    pub pos: String,
+    /// 开仓平均价
+    #[serde(rename = "avgPx")]
+    pub avg_px: String,
+    /// 未实现收益
+    #[serde(rename = "upl")]
+    pub upl: String,
+    /// 未实现收益率
+    #[serde(rename = "uplRatio")]
+    pub upl_ratio: String,
+    /// 杠杆倍数
+    pub lever: String,
+    /// 标记价格
+    #[serde(rename = "markPx")]
+    pub mark_px: String,
+    /// 最新成交价
+    #[serde(rename = "lastPx")]
+    pub last_px: Option<String>,
+    /// 最新成交价时间
+    #[serde(rename = "lastPxTime")]
+    pub last_px_time: Option<String>,
+    /// 持仓价值
+    #[serde(rename = "posValue")]
+    pub pos_value: Option<String>,
+    /// 保证金
+    pub margin: Option<String>,
+    /// 保证金率
+    #[serde(rename = "mgnRatio")]
+    pub mgn_ratio: Option<String>,
+    /// 维持保证金率
+    #[serde(rename = "maintMarginRatio")]
+    pub maint_margin_ratio: Option<String>,
+    /// 强平价格
+    #[serde(rename = "liqPx")]
+    pub liq_px: Option<String>,
+    /// 利息
+    pub interest: Option<String>,
+    /// 资金费用
+    #[serde(rename = "fundingFee")]
+    pub funding_fee: Option<String>,
+    /// 资金费用时间
+    #[serde(rename = "fundingTime")]
+    pub funding_time: Option<String>,
+    /// 方向
+    pub direction: Option<String>,
+    /// 币种
+    pub ccy: Option<String>,
+    /// 持仓时间
+    #[serde(rename = "cTime")]
+    pub c_time: String,
+    /// 仓位更新时间
+    #[serde(rename = "uTime")]
+    pub u_time: String,
+    /// 标的
+    pub uly: Option<String>,
+    /// 期权价值
+    #[serde(rename = "optVal")]
+    pub opt_val: Option<String>,
+    /// 期权价值时间
+    #[serde(rename = "optValTime")]
+    pub opt_val_time: Option<String>,
+    /// 期权价值标记价格
+    #[serde(rename = "optValMarkPx")]
+    pub opt_val_mark_px: Option<String>,
+    /// 期权价值最新价格
+    #[serde(rename = "optValLastPx")]
+    pub opt_val_last_px: Option<String>,
+    /// 期权价值最新价格时间
+    #[serde(rename = "optValLastPxTime")]
+    pub opt_val_last_px_time: Option<String>,
+    /// 期权价值持仓价值
+    #[serde(rename = "optValPosValue")]
+    pub opt_val_pos_value: Option<String>,
+    /// 期权价值保证金
+    #[serde(rename = "optValMargin")]
+    pub opt_val_margin: Option<String>,
+    /// 期权价值保证金率
+    #[serde(rename = "optValMgnRatio")]
+    pub opt_val_mgn_ratio: Option<String>,
+    /// 期权价值维持保证金率
+    #[serde(rename = "optValMaintMarginRatio")]
+    pub opt_val_maint_margin_ratio: Option<String>,
+    /// 期权价值强平价格
+    #[serde(rename = "optValLiqPx")]
+    pub opt_val_liq_px: Option<String>,
+    /// 期权价值利息
+    #[serde(rename = "optValInterest")]
+    pub opt_val_interest: Option<String>,
+    /// 期权价值资金费用
+    #[serde(rename = "optValFundingFee")]
+    pub opt_val_funding_fee: Option<String>,
+    /// 期权价值资金费用时间
+    #[serde(rename = "optValFundingTime")]
+    pub opt_val_funding_time: Option<String>,
+}
+
+impl From<&super::PositionHistory> for PositionHistory {
+    fn from(history: &super::PositionHistory) -> Self {
+        Self {
+            inst_type: history.inst_type.to_string(),
+            inst_id: history.inst_id.clone(),
+            mgn_mode: history.mgn_mode.to_string(),
+            pos_id: history.pos_id.clone(),
+            pos_side: history.pos_side.to_string(),
+            open_avg_px: decimal_text(&history.open_avg_px),
+            close_avg_px: decimal_text(&history.close_avg_px),
+            realized_pnl: decimal_text(&history.realized_pnl),
+            close_type: history.close_type.to_string(),
+            open_time: time_text(&history.open_time),
+            u_time: time_text(&history.u_time),
+            lever: decimal_text(&history.lever),
+            fee: decimal_text(&history.fee),
+            funding_fee: decimal_text(&history.funding_fee),
+            direction: history.direction.to_string(),
+            ccy: history.ccy.clone(),
+            close_total_pos: decimal_text(&history.close_total_pos),
+            open_max_pos: decimal_text(&history.open_max_pos),
+            pnl: decimal_text(&history.pnl),
+            pnl_ratio: decimal_text(&history.pnl_ratio),
+            liq_penalty: decimal_text(&history.liq_penalty),
+            non_settle_avg_px: decimal_text(&history.non_settle_avg_px),
+            settled_pnl: decimal_text(&history.settled_pnl),
+            trigger_px: decimal_text(&history.trigger_px),
+            uly: history.uly.clone(),
+        }
+    }
+}
+
+impl From<&super::Position> for Position {
+    fn from(position: &super::Position) -> Self {
+        // 与OKX原始响应一致，可选的数值和时间字段为空时是空字符串而不是 `None`
+        let decimal = |value: &Option<Decimal>| Some(decimal_text(value));
+        let time =
+            |value: &Option<DateTime<Utc>>| Some(value.as_ref().map(time_text).unwrap_or_default());
+
+        Self {
+            inst_type: position.inst_type.to_string(),
+            inst_id: position.inst_id.clone(),
+            mgn_mode: position.mgn_mode.to_string(),
+            pos_id: position.pos_id.clone(),
+            pos_side: position.pos_side.to_string(),
+            pos: decimal_text(&position.pos),
+            avg_px: decimal_text(&position.avg_px),
+            upl: decimal_text(&position.upl),
+            upl_ratio: decimal_text(&position.upl_ratio),
+            lever: decimal_text(&position.lever),
+            mark_px: decimal_text(&position.mark_px),
+            last_px: decimal(&position.last_px),
+            last_px_time: time(&position.last_px_time),
+            pos_value: decimal(&position.pos_value),
+            margin: decimal(&position.margin),
+            mgn_ratio: decimal(&position.mgn_ratio),
+            maint_margin_ratio: decimal(&position.maint_margin_ratio),
+            liq_px: decimal(&position.liq_px),
+            interest: decimal(&position.interest),
+            funding_fee: decimal(&position.funding_fee),
+            funding_time: time(&position.funding_time),
+            direction: position.direction.as_ref().map(|d| d.to_string()),
+            ccy: position.ccy.clone(),
+            c_time: time_text(&position.c_time),
+            u_time: time_text(&position.u_time),
+            uly: position.uly.clone(),
+            opt_val: decimal(&position.opt_val),
+            opt_val_time: time(&position.opt_val_time),
+            opt_val_mark_px: decimal(&position.opt_val_mark_px),
+            opt_val_last_px: decimal(&position.opt_val_last_px),
+            opt_val_last_px_time: time(&position.opt_val_last_px_time),
+            opt_val_pos_value: decimal(&position.opt_val_pos_value),
+            opt_val_margin: decimal(&position.opt_val_margin),
+            opt_val_mgn_ratio: decimal(&position.opt_val_mgn_ratio),
+            opt_val_maint_margin_ratio: decimal(&position.opt_val_maint_margin_ratio),
+            opt_val_liq_px: decimal(&position.opt_val_liq_px),
+            opt_val_interest: decimal(&position.opt_val_interest),
+            opt_val_funding_fee: decimal(&position.opt_val_funding_fee),
+            opt_val_funding_time: time(&position.opt_val_funding_time),
+        }
+    }
+}
+
+/// 十进制数按原始精度转为字符串，`None` 为空字符串
+fn decimal_text(value: &Option<Decimal>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// 时间转为毫秒时间戳字符串
+fn time_text(value: &DateTime<Utc>) -> String {
+    value.timestamp_millis().to_string()
+}